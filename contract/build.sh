@@ -4,3 +4,7 @@ echo ">> Building contract"
 
 rustup target add wasm32-unknown-unknown
 cargo build --all --target wasm32-unknown-unknown --release  && rm -rf ../dist && mkdir ../dist && cp ./target/wasm32-unknown-unknown/release/axelar_cgp_near.wasm ../dist/
+
+echo ">> Building contract with test utils and verbose logs"
+
+cargo build --all --target wasm32-unknown-unknown --profile test-utils --features test-utils,verbose-logs && cp ./target/wasm32-unknown-unknown/test-utils/axelar_cgp_near.wasm ../dist/axelar_cgp_near_test_utils.wasm
@@ -0,0 +1,11 @@
+#![no_main]
+
+use axelar_cgp_near::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+// Any proof bytes must either decode or be reported as `None`, never abort the view
+fuzz_target!(|proof: &[u8]| {
+    let contract = fuzzing::contract(vec![]);
+
+    let _ = fuzzing::decode_proof(&contract, proof);
+});
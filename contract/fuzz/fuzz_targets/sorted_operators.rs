@@ -0,0 +1,30 @@
+#![no_main]
+
+use axelar_cgp_near::fuzzing::{self, H160};
+use libfuzzer_sys::fuzz_target;
+use std::collections::BTreeSet;
+
+// The operators must be accepted if and only if they are strictly ascending, which is the order of
+// the deduplicated `BTreeSet` of the same operators, and the first of them is not the zero address
+fuzz_target!(|data: &[u8]| {
+    let operators = data
+        .chunks_exact(20)
+        .map(H160::from_slice)
+        .collect::<Vec<_>>();
+
+    let sorted = operators.iter().copied().collect::<BTreeSet<_>>();
+    let is_valid =
+        !operators.is_empty() && !operators[0].is_zero() && sorted.iter().eq(operators.iter());
+
+    assert_eq!(
+        fuzzing::is_sorted_asc_and_contains_no_duplicate(&operators),
+        is_valid
+    );
+
+    let mut contract = fuzzing::contract(vec![]);
+
+    assert_eq!(
+        fuzzing::transfer_operators(&mut contract, &operators).is_ok(),
+        is_valid
+    );
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use axelar_cgp_near::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+// Any params bytes must either be applied or be rejected with an `AuthError`, never abort the call
+fuzz_target!(|params: &[u8]| {
+    let mut contract = fuzzing::contract(vec![]);
+
+    let _ = fuzzing::transfer_operatorship(&mut contract, params);
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use axelar_cgp_near::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+// Any proof bytes must either validate or be rejected with a `ProofError`, never abort the call
+fuzz_target!(|proof: &[u8]| {
+    // The `(address[], uint256[], uint256)` params of the operator `0x...01` with weight and
+    // threshold 1, each word only has its last byte set
+    let operators = [0x60, 0xa0, 1, 1, 1, 1, 1]
+        .into_iter()
+        .flat_map(|byte| {
+            let mut word = [0u8; 32];
+            word[31] = byte;
+            word
+        })
+        .collect();
+    let contract = fuzzing::contract(vec![operators]);
+
+    let _ = fuzzing::validate_proof(&contract, [1; 32], proof);
+});
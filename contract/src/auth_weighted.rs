@@ -1,211 +1,1477 @@
-use crate::events::OperatorshipTransferredEvent;
-use crate::utils::{self, abi_encode, clean_payload, to_h256};
+use crate::errors::{AuthError, ProofError, SignatureError};
+use crate::events::{
+    EmergencyStateSetEvent, KeySetRevokedEvent, OperatorshipTransferredEvent, ProofValidatedEvent,
+    ProofValidationFailedEvent,
+};
+use crate::utils::{
+    self, clean_payload, encode_operators, to_h256, try_clean_payload, EthAddress, KeyType,
+    OperatorKey, Weight,
+};
 use crate::{utils::abi_decode, utils::keccak256, Axelar, AxelarExt};
-use ethabi::ethereum_types::{H160, H256};
-use ethabi::{Address, Token};
+use ethabi::ethereum_types::{H160, H256, U256};
+use ethabi::Token;
 use near_contract_tools::owner::*;
-use near_sdk::env;
+use near_contract_tools::pause::Pause;
+use near_sdk::FunctionError;
 
 use ethabi::ParamType;
 use near_contract_tools::standard::nep297::Event;
-use near_sdk::near_bindgen;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSchema, BorshSerialize};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, Balance, Promise};
 
-/// A constant that is used to determine how many epochs old keys are valid for.
+/// A constant that is used to determine how many epochs old keys are valid for by default.
 pub const OLD_KEY_RETENTION: u8 = 16;
 
+/// The maximum number of signatures a proof can carry by default, which bounds the gas spent on
+/// recovering the signers.
+pub const DEFAULT_MAX_SIGNATURES: u64 = 64;
+
+/// The minimum number of operators an operatorship can be transferred to by default.
+pub const DEFAULT_MIN_OPERATORS: u32 = 1;
+
+/// The flag of a threshold expressed as a fraction of the total weight, the highest bit of the
+/// threshold, the other bits are the fraction in basis points.
+pub const FRACTIONAL_THRESHOLD_FLAG: U256 = U256([0, 0, 0, 1 << 63]);
+
+/// The basis points of the whole total weight.
+pub const MAX_BASIS_POINTS: u64 = 10_000;
+
+/// The maximum number of epochs returned by a single `operator_history` call.
+pub const MAX_OPERATOR_HISTORY_LIMIT: u64 = 100;
+
+/// `ValidatedProof` tells which operators signed a proof accepted by `validate_proof_epoch`.
+///
+/// Properties:
+///
+/// * `is_current_operators`: Whether the proof was signed by the current operators.
+/// * `operators_epoch`: The epoch of the operators that signed the proof.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatedProof {
+    pub is_current_operators: bool,
+    pub operators_epoch: u64,
+}
+
+/// `TransferResult` is returned by the operatorship transfers once the new operators are registered.
+///
+/// Properties:
+///
+/// * `epoch`: The epoch assigned to the new operators.
+/// * `operators_hash`: The hash of the new operators, the proofs of the new operators resolve to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferResult {
+    pub epoch: u64,
+    pub operators_hash: String,
+}
+
+/// `AuthHealth` summarizes the invariants of the auth state for the watchdogs monitoring it.
+///
+/// Properties:
+///
+/// * `current_epoch`: The current epoch number.
+/// * `old_key_retention`: The number of epochs for which the previous operators are still valid.
+/// * `has_current_operators`: Whether the hash of the current operators is stored, `false` if the
+///   contract has no operators yet or the epoch advanced without storing them.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuthHealth {
+    pub current_epoch: u64,
+    pub old_key_retention: u64,
+    pub has_current_operators: bool,
+}
+
+/// `OperatorSet` is the decoded operators of an epoch, stored while the epoch is within the key
+/// retention window as only the hash of the operators is needed to validate the proofs.
+///
+/// Properties:
+///
+/// * `operators`: The addresses of the operators, sorted in ascending order.
+/// * `weights`: The weight of each operator.
+/// * `threshold`: The absolute threshold of the operators.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorSet {
+    pub operators: Vec<EthAddress>,
+    pub weights: Vec<Weight>,
+    pub threshold: Weight,
+}
+
+/// `DecodedProof` is the content of an ABI encoded proof, as returned by `decode_proof`.
+///
+/// Properties:
+///
+/// * `operators`: The addresses of the operators, in the order of the proof.
+/// * `weights`: The weight of each operator.
+/// * `threshold`: The threshold of the operators.
+/// * `signature_count`: The number of signatures of the proof.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DecodedProof {
+    pub operators: Vec<EthAddress>,
+    pub weights: Vec<Weight>,
+    pub threshold: Weight,
+    pub signature_count: u32,
+}
+
+/// `ProofStatus` tells whether a proof would be accepted by `validate_proof`, and why not.
+///
+/// Variants:
+///
+/// * `ValidCurrent`: The proof is signed by the current operators.
+/// * `ValidRetained`: The proof is signed by operators that are still within the key retention window.
+/// * `ExpiredEpoch`: The operators epoch is older than the key retention window.
+/// * `ExpiredProof`: The operators epoch was created longer than the maximum proof age ago.
+/// * `UnknownOperators`: The operators hash of the proof was never registered.
+/// * `InsufficientWeight`: The signatures are valid, but their weight is less than the threshold.
+/// * `InvalidSignatures`: A signature could not be recovered, or its signer is not one of the
+///   operators in order.
+/// * `Malformed`: The message hash or the proof can not be decoded.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ProofStatus {
+    ValidCurrent,
+    ValidRetained,
+    ExpiredEpoch,
+    ExpiredProof,
+    UnknownOperators,
+    InsufficientWeight,
+    InvalidSignatures,
+    Malformed,
+}
+
+impl From<Result<ValidatedProof, ProofError>> for ProofStatus {
+    fn from(result: Result<ValidatedProof, ProofError>) -> Self {
+        match result {
+            Ok(proof) if proof.is_current_operators => ProofStatus::ValidCurrent,
+            Ok(_) => ProofStatus::ValidRetained,
+            Err(ProofError::MalformedProof) => ProofStatus::Malformed,
+            Err(ProofError::UnknownOperators) => ProofStatus::UnknownOperators,
+            Err(ProofError::ExpiredEpoch) => ProofStatus::ExpiredEpoch,
+            Err(ProofError::ExpiredProof) => ProofStatus::ExpiredProof,
+            Err(ProofError::InvalidSignatures(SignatureError::LowSignatureWeight)) => {
+                ProofStatus::InsufficientWeight
+            }
+            Err(ProofError::InvalidSignatures(_)) => ProofStatus::InvalidSignatures,
+        }
+    }
+}
+
 /// Axelar Authentication Weighted Implementation
 #[near_bindgen]
 impl Axelar {
-    /// It takes an epoch number and returns the hash of the block that was mined at that epoch
+    /// `current_epoch` returns the epoch of the current operators
+    ///
+    /// Returns:
+    ///
+    /// The current epoch.
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    /// `old_key_retention` returns the number of epochs for which the previous operators are still
+    /// valid
+    ///
+    /// Returns:
+    ///
+    /// The key retention in epochs.
+    pub fn old_key_retention(&self) -> u64 {
+        self.old_key_retention
+    }
+
+    /// `max_proof_age` returns the number of nanoseconds after its creation for which an epoch can
+    /// validate proofs
+    ///
+    /// Returns:
+    ///
+    /// The maximum proof age in nanoseconds, or `None` if proofs are only bound by the key retention.
+    pub fn max_proof_age(&self) -> Option<u64> {
+        self.max_proof_age
+    }
+
+    /// `max_signatures` returns the maximum number of signatures a proof can carry
+    ///
+    /// Returns:
+    ///
+    /// The maximum number of signatures.
+    pub fn max_signatures(&self) -> u64 {
+        self.max_signatures
+    }
+
+    /// `health` returns a summary of the auth state for monitoring
+    ///
+    /// Returns:
+    ///
+    /// The `AuthHealth` of the contract.
+    pub fn health(&self) -> AuthHealth {
+        AuthHealth {
+            current_epoch: self.current_epoch,
+            old_key_retention: self.old_key_retention,
+            has_current_operators: self.hash_for_epoch.contains_key(&self.current_epoch),
+        }
+    }
+
+    /// `min_operators` returns the minimum number of operators an operatorship can be transferred to
+    ///
+    /// Returns:
+    ///
+    /// The minimum number of operators.
+    pub fn min_operators(&self) -> u32 {
+        self.min_operators
+    }
+
+    /// `address_for_public_key` returns the operator address of a secp256k1 public key, as the EVM
+    /// derives it
+    ///
+    /// Arguments:
+    ///
+    /// * `public_key`: The hex encoded uncompressed public key, with or without the `0x04` prefix.
+    ///
+    /// Returns:
+    ///
+    /// The hex encoded address, or `None` if the public key is not 64 bytes, or 65 bytes with the
+    /// `0x04` prefix.
+    pub fn address_for_public_key(&self, public_key: String) -> Option<EthAddress> {
+        let public_key = try_clean_payload(public_key)?;
+
+        let public_key = match public_key.len() {
+            64 => &public_key[..],
+            65 if public_key[0] == 0x04 => &public_key[1..],
+            _ => return None,
+        };
+
+        Some(utils::public_key_to_address(public_key).into())
+    }
+
+    /// `current_operators` returns the operators, weights and threshold of the current epoch
+    ///
+    /// Returns:
+    ///
+    /// The `OperatorSet` of the current epoch, or `None` if there are no operators or they were
+    /// registered before the operator sets were stored.
+    pub fn current_operators(&self) -> Option<OperatorSet> {
+        self.operator_set_for_epoch.get(&self.current_epoch)
+    }
+
+    /// `min_signers_for_threshold` returns the smallest number of current operators whose weight
+    /// reaches the threshold, so relayers can keep the proofs small
+    ///
+    /// Returns:
+    ///
+    /// The number of the highest weight operators needed to reach the threshold, or `None` if the
+    /// operators of the current epoch are not stored.
+    pub fn min_signers_for_threshold(&self) -> Option<u32> {
+        let operator_set = self.current_operators()?;
+
+        let mut weights = operator_set
+            .weights
+            .into_iter()
+            .map(U256::from)
+            .collect::<Vec<_>>();
+
+        weights.sort_unstable_by(|a, b| b.cmp(a));
+
+        let threshold = U256::from(operator_set.threshold);
+        let mut total_weight = U256::zero();
+
+        for (index, weight) in weights.into_iter().enumerate() {
+            total_weight += weight;
+
+            if total_weight >= threshold {
+                return Some(index as u32 + 1);
+            }
+        }
+
+        None
+    }
+
+    /// It takes an epoch number and returns the hash of the operators registered at that epoch
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch number for which you want to get the hash.
+    ///
+    /// Returns:
+    ///
+    /// The hash of the operators at the given epoch, or `None` if the epoch was never set.
+    pub fn hash_for_epoch(&self, epoch: u64) -> Option<String> {
+        self.hash_for_epoch
+            .get(&epoch)
+            .map(utils::to_eth_hex_string)
+    }
+
+    /// `operator_history` returns the hash of the operators of a range of epochs, so the rotations
+    /// can be paginated
+    ///
+    /// Arguments:
+    ///
+    /// * `from_epoch`: The first epoch of the range.
+    /// * `limit`: The number of epochs in the range, capped at `MAX_OPERATOR_HISTORY_LIMIT`.
+    ///
+    /// Returns:
+    ///
+    /// The epoch and operators hash pairs of the range, epochs without operators are skipped.
+    pub fn operator_history(&self, from_epoch: u64, limit: u64) -> Vec<(u64, String)> {
+        let to_epoch = from_epoch
+            .saturating_add(limit.min(MAX_OPERATOR_HISTORY_LIMIT))
+            .min(self.current_epoch.saturating_add(1));
+
+        (from_epoch..to_epoch)
+            .filter_map(|epoch| {
+                self.hash_for_epoch
+                    .get(&epoch)
+                    .map(|hash| (epoch, utils::to_eth_hex_string(hash)))
+            })
+            .collect()
+    }
+
+    /// `epoch_for_hash` returns the epoch number for a given hash
+    ///
+    /// Arguments:
+    ///
+    /// * `hash`: The hash of the operators to get the epoch for.
+    ///
+    /// Returns:
+    ///
+    /// The epoch for the hash, or `None` if the operators were never registered.
+    pub fn epoch_for_hash(&self, hash: String) -> Option<u64> {
+        let hash: [u8; 32] = clean_payload(hash).try_into().ok()?;
+        self.epoch_for_hash.get(&hash)
+    }
+
+    /// `is_current_operators` checks whether the given operators are the current operators
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The ABI encoded operators, weights and threshold.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the params are malformed or not the current operators.
+    pub fn is_current_operators(&self, params: String) -> bool {
+        let expected_output_types = vec![
+            ParamType::Array(Box::new(ParamType::Address)),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Uint(256),
+        ];
+
+        let params = match try_clean_payload(params) {
+            Some(params) => params,
+            None => return false,
+        };
+
+        if abi_decode(&params, &expected_output_types).is_err() {
+            return false;
+        }
+
+        self.epoch_for_hash.get(&keccak256(params)) == Some(self.current_epoch)
+    }
+
+    /// If the epoch of the operators is the same as the current epoch, and the epoch of the operators
+    /// is not too old, then validate the signatures
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// `Ok(true)` if the proof was signed by the current operators, `Ok(false)` if it was signed by
+    /// operators that are still within the key retention window, or if its signers are not the
+    /// operators or do not reach the threshold, so a well-formed but invalid proof is rejected
+    /// without aborting the call. A `ProofError` if the proof is malformed, its operators are
+    /// unknown or expired, or a signature is invalid. `validate_proof_epoch` and
+    /// `can_validate_proof` tell the rejected proofs apart from the retained operators.
+    #[handle_result]
+    pub fn validate_proof(&self, message_hash: String, proof: String) -> Result<bool, ProofError> {
+        let result = self.validate_proof_epoch(message_hash.clone(), proof);
+
+        Self::internal_proof_result(message_hash, result)
+    }
+
+    /// `validate_proof_legacy` validates the proof like `validate_proof`, with its former
+    /// signature: it returns `true` only for a proof of the current operators and panics with the
+    /// `ProofError` otherwise. Deprecated, use `validate_proof`.
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value.
+    #[deprecated(note = "use validate_proof")]
+    pub fn validate_proof_legacy(&self, message_hash: String, proof: String) -> bool {
+        self.validate_proof_epoch(message_hash, proof)
+            .map(|proof| proof.is_current_operators)
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// `validate_proof_epoch` validates the proof the same way as `validate_proof`, and also tells
+    /// the epoch of the operators that signed it, so the gateway can record which operators
+    /// authorized a command batch
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    #[handle_result]
+    pub fn validate_proof_epoch(
+        &self,
+        message_hash: String,
+        proof: String,
+    ) -> Result<ValidatedProof, ProofError> {
+        self.internal_validate_proof(message_hash, clean_payload(proof))
+    }
+
+    /// `validate_proof_logged` validates the proof the same way as `validate_proof_epoch`, and also
+    /// emits a `ProofValidatedEvent` when the proof is accepted, so off-chain systems can attribute
+    /// an action to the operators that authorized it. It is a call rather than a view, the gateway
+    /// validates the proofs of the command batches with it.
+    ///
+    /// A rejected proof emits a `ProofValidationFailedEvent` before the call fails, as the gateway
+    /// only rejects the batch when this call fails. The event is therefore logged by a failed
+    /// receipt, and is only visible in the outcome of the gateway `execute` transaction.
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    #[handle_result]
+    pub fn validate_proof_logged(
+        &mut self,
+        message_hash: String,
+        proof: String,
+    ) -> Result<ValidatedProof, ProofError> {
+        let validated_proof =
+            self.internal_validate_proof_with_event(message_hash.clone(), clean_payload(proof))?;
+
+        ProofValidatedEvent {
+            message_hash,
+            operators_epoch: validated_proof.operators_epoch,
+            is_current: validated_proof.is_current_operators,
+        }
+        .emit();
+
+        Ok(validated_proof)
+    }
+
+    /// `validate_proof_base64` is the same as `validate_proof`, but it takes the proof as base64
+    /// instead of `0x` prefixed hex, the encoding NEAR JSON-RPC clients use for binary arguments
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The `0x` prefixed hex hash of the message to be signed.
+    /// * `proof`: The base64 encoded ABI proof.
+    ///
+    /// Returns:
+    ///
+    /// The same result as `validate_proof`.
+    #[handle_result]
+    pub fn validate_proof_base64(
+        &self,
+        message_hash: String,
+        proof: Base64VecU8,
+    ) -> Result<bool, ProofError> {
+        let result = self.internal_validate_proof(message_hash.clone(), proof.into());
+
+        Self::internal_proof_result(message_hash, result)
+    }
+
+    /// `validate_proof_with_params` validates a proof submitted as the operators params it was
+    /// registered with and the signatures, instead of a full proof. The params are hashed as they
+    /// are to look up the epoch of the operators, and only decoded once the epoch is valid to match
+    /// the signers.
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `operators_params`: The ABI encoded operators, weights and threshold, as registered.
+    /// * `signatures`: The signatures of the operators, in the order of the operators.
+    ///
+    /// Returns:
+    ///
+    /// The same result as `validate_proof`.
+    #[handle_result]
+    pub fn validate_proof_with_params(
+        &self,
+        message_hash: String,
+        operators_params: String,
+        signatures: Vec<String>,
+    ) -> Result<bool, ProofError> {
+        let result = self.internal_validate_proof_with_params(
+            message_hash.clone(),
+            clean_payload(operators_params),
+            signatures.into_iter().map(clean_payload).collect(),
+        );
+
+        Self::internal_proof_result(message_hash, result)
+    }
+
+    /// `validate_proof_ed25519` validates the proof the same way as `validate_proof`, signed by
+    /// operators registered with `transfer_operatorship_ed25519`
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The ABI encoded `(bytes32[], uint256[], uint256, bytes[])` proof. Each signature
+    ///   is the 32-byte public key of its signer followed by the 64-byte Ed25519 signature of the
+    ///   message hash, in the order of the operators.
+    ///
+    /// Returns:
+    ///
+    /// The same result as `validate_proof`.
+    #[handle_result]
+    pub fn validate_proof_ed25519(
+        &self,
+        message_hash: String,
+        proof: String,
+    ) -> Result<bool, ProofError> {
+        let result =
+            self.internal_validate_proof_of::<H256>(message_hash.clone(), clean_payload(proof));
+
+        Self::internal_proof_result(message_hash, result)
+    }
+
+    /// `can_validate_proof` checks the proof the same way as `validate_proof` without panicking or
+    /// emitting an event, so relayers can dry-run a proof before paying to submit it
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// The `ProofStatus` of the proof, `Malformed` if the message hash or the proof are not hex
+    /// encoded.
+    pub fn can_validate_proof(&self, message_hash: String, proof: String) -> ProofStatus {
+        let is_hash = try_clean_payload(message_hash.clone()).is_some_and(|hash| hash.len() == 32);
+
+        match try_clean_payload(proof) {
+            Some(proof) if is_hash => self.internal_validate_proof(message_hash, proof).into(),
+            _ => ProofStatus::Malformed,
+        }
+    }
+
+    /// `decode_proof` decodes a proof into its operators, weights, threshold and number of
+    /// signatures, so a rejected proof can be inspected. It never panics, and neither validates the
+    /// signatures nor checks that the operators are registered.
+    ///
+    /// Arguments:
+    ///
+    /// * `proof`: The ABI encoded proof.
+    ///
+    /// Returns:
+    ///
+    /// The `DecodedProof`, or `None` if the proof is not hex or not a valid ABI encoding.
+    pub fn decode_proof(&self, proof: String) -> Option<DecodedProof> {
+        let expected_output_types = vec![
+            ParamType::Array(Box::new(ParamType::Address)),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::Bytes)),
+        ];
+
+        let proof = try_clean_payload(proof)?;
+        let mut tokens = abi_decode(&proof, &expected_output_types).ok()?.into_iter();
+
+        let operators = tokens
+            .next()?
+            .into_array()?
+            .into_iter()
+            .map(|token| token.into_address().map(EthAddress::from))
+            .collect::<Option<Vec<_>>>()?;
+        let weights = tokens
+            .next()?
+            .into_array()?
+            .into_iter()
+            .map(|token| token.into_uint().map(Weight::from))
+            .collect::<Option<Vec<_>>>()?;
+        let threshold = tokens.next()?.into_uint()?.into();
+        let signature_count = tokens.next()?.into_array()?.len().try_into().ok()?;
+
+        Some(DecodedProof {
+            operators,
+            weights,
+            threshold,
+            signature_count,
+        })
+    }
+
+    /// It checks that the signatures of the given operators reach the threshold, without checking
+    /// the epoch of the operators
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `operators`: The list of operator addresses, sorted in ascending order.
+    /// * `weights`: The weight of each operator.
+    /// * `threshold`: The minimum weight required to validate the message.
+    /// * `signatures`: A list of signatures, in the same order as the operators.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the operators are not sorted, the message hash or a signature is
+    /// not `0x` prefixed hex, or the signatures are invalid or do not reach the threshold.
+    pub fn validate_signatures(
+        &self,
+        message_hash: String,
+        operators: Vec<EthAddress>,
+        weights: Vec<u32>,
+        threshold: u32,
+        signatures: Vec<String>,
+    ) -> bool {
+        if operators.len() != weights.len() {
+            return false;
+        }
+
+        let operators = operators.into_iter().map(H160::from).collect::<Vec<_>>();
+
+        if !Axelar::internal_is_sorted_asc_and_contains_no_duplicate(&operators) {
+            return false;
+        }
+
+        let message_hash = match try_clean_payload(message_hash) {
+            Some(hash) if hash.len() == 32 => H256::from_slice(&hash),
+            _ => return false,
+        };
+
+        let signatures = match signatures
+            .into_iter()
+            .map(|signature| try_clean_payload(signature).map(Token::Bytes))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(signatures) => signatures,
+            None => return false,
+        };
+
+        self.internal_validate_signatures(message_hash, operators, weights, threshold, signatures)
+            .is_ok()
+    }
+
+    /// `transfer_operatorship_signed` transfers the operatorship to new operators without the owner,
+    /// as authorized by a proof of the current operators over the params. Operators that are only
+    /// retained can still validate proofs, but can not rotate the keys. The signed message binds the
+    /// params to this contract and to the current epoch, so a proof can not be replayed on another
+    /// deployment, nor once the operators it registered were pruned or revoked.
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The ABI encoded operators, weights and threshold.
+    /// * `proof`: The proof over the message hash, in the `DOMAIN_OPERATORSHIP_TRANSFER` domain, of
+    ///   the ABI encoded account id of this contract, current epoch and params.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` with the epoch and the hash of the new operators.
+    pub fn transfer_operatorship_signed(
+        &mut self,
+        params: String,
+        proof: String,
+    ) -> TransferResult {
+        let params = clean_payload(params);
+        let message_hash = utils::to_eth_hex_string(utils::to_domain_message_hash(
+            utils::DOMAIN_OPERATORSHIP_TRANSFER,
+            &utils::abi_encode(vec![
+                Token::String(env::current_account_id().to_string()),
+                Token::Uint(U256::from(self.current_epoch)),
+                Token::Bytes(params.clone()),
+            ]),
+        ));
+
+        let validated_proof = self
+            .internal_validate_proof(message_hash, clean_payload(proof))
+            .unwrap_or_else(|error| error.panic());
+
+        if !validated_proof.is_current_operators {
+            AuthError::NotCurrentOperators.panic();
+        }
+
+        self.internal_transfer_operatorship(params)
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// Only owner
+
+    /// `transfer_operatorship` is a public function that requires the caller to be the owner, and then
+    /// calls the internal function `internal_transfer_operatorship`
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: Vec<u8>
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` with the epoch and the hash of the new operators.
+    #[payable]
+    pub fn transfer_operatorship(&mut self, params: String) -> TransferResult {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.internal_transfer_operatorship(clean_payload(params))
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// `transfer_operatorship_base64` is the same as `transfer_operatorship`, but it takes the params
+    /// as base64 instead of `0x` prefixed hex, the encoding NEAR JSON-RPC clients use for binary
+    /// arguments
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The base64 encoded ABI operators, weights and threshold.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` with the epoch and the hash of the new operators.
+    #[payable]
+    pub fn transfer_operatorship_base64(&mut self, params: Base64VecU8) -> TransferResult {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.internal_transfer_operatorship(params.into())
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// `transfer_operatorship_batch` applies several operatorship transfers in order, each of them
+    /// incrementing the epoch and emitting its own event. If any of the params is invalid the whole
+    /// batch is reverted.
+    ///
+    /// Arguments:
+    ///
+    /// * `params_list`: The ABI encoded operators, weights and threshold of each transfer.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` of each transfer, in the order of the params.
+    #[payable]
+    pub fn transfer_operatorship_batch(&mut self, params_list: Vec<String>) -> Vec<TransferResult> {
+        Self::require_owner();
+        self.require_no_timelock();
+
+        params_list
+            .into_iter()
+            .map(|params| {
+                self.internal_transfer_operatorship(clean_payload(params))
+                    .unwrap_or_else(|error| error.panic())
+            })
+            .collect()
+    }
+
+    /// `transfer_operatorship_typed` is the same as `transfer_operatorship`, but it takes the
+    /// operators, weights and threshold as arguments and builds the ABI encoded params itself
+    ///
+    /// Arguments:
+    ///
+    /// * `operators`: The list of operator addresses, sorted in ascending order.
+    /// * `weights`: The weight of each operator, as a number or a decimal string.
+    /// * `threshold`: The minimum weight required to validate a proof, as a number or a decimal
+    ///   string.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` with the epoch and the hash of the new operators.
+    #[payable]
+    pub fn transfer_operatorship_typed(
+        &mut self,
+        operators: Vec<EthAddress>,
+        weights: Vec<Weight>,
+        threshold: Weight,
+    ) -> TransferResult {
+        Self::require_owner();
+        self.require_no_timelock();
+
+        let params = encode_operators(
+            &operators.into_iter().map(H160::from).collect::<Vec<_>>(),
+            &weights.into_iter().map(U256::from).collect::<Vec<_>>(),
+            threshold.into(),
+        );
+
+        self.internal_transfer_operatorship(params)
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// `transfer_operatorship_ed25519` is the same as `transfer_operatorship`, to operators that
+    /// sign with Ed25519 keys. Their proofs are validated with `validate_proof_ed25519`, and as
+    /// only the hash of the operators is stored, `current_operators` returns `None` for their epoch.
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The ABI encoded `(bytes32[], uint256[], uint256)` public keys of the operators,
+    ///   sorted in ascending order, weights and threshold.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` with the epoch and the hash of the new operators, tagged with the
+    /// Ed25519 key type.
+    #[payable]
+    pub fn transfer_operatorship_ed25519(&mut self, params: String) -> TransferResult {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.internal_transfer_operatorship_of::<H256>(clean_payload(params))
+            .unwrap_or_else(|error| error.panic())
+    }
+
+    /// `set_max_proof_age` updates the number of nanoseconds after its creation for which an epoch
+    /// can validate proofs
+    ///
+    /// Arguments:
+    ///
+    /// * `max_proof_age_ns`: The maximum proof age in nanoseconds, `None` removes the limit.
+    #[payable]
+    pub fn set_max_proof_age(&mut self, max_proof_age_ns: Option<u64>) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.max_proof_age = max_proof_age_ns;
+    }
+
+    /// `set_max_signatures` updates the maximum number of signatures a proof can carry
+    ///
+    /// Arguments:
+    ///
+    /// * `max_signatures`: The maximum number of signatures.
+    #[payable]
+    pub fn set_max_signatures(&mut self, max_signatures: u64) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.max_signatures = max_signatures;
+    }
+
+    /// `prune_expired` removes the operators of the epochs that are out of the key retention window,
+    /// and refunds the released storage to the owner. Pruned operators can be registered again.
+    /// Each call starts after the last epoch the previous one went through, so the gas it spends is
+    /// bounded by `up_to_epoch`, not by the number of epochs since the first one.
+    ///
+    /// Arguments:
+    ///
+    /// * `up_to_epoch`: The last epoch to prune, epochs still within the key retention window are
+    ///   never pruned.
+    ///
+    /// Returns:
+    ///
+    /// The number of epochs that were pruned.
+    #[payable]
+    pub fn prune_expired(&mut self, up_to_epoch: u64) -> u64 {
+        Self::require_owner();
+
+        let last_expired_epoch = self.current_epoch.saturating_sub(self.old_key_retention);
+        let last_epoch = up_to_epoch.min(last_expired_epoch);
+        let initial_storage_usage = env::storage_usage();
+        let mut pruned = 0;
+
+        for epoch in self.last_pruned_epoch.saturating_add(1)..=last_epoch {
+            if let Some(hash) = self.hash_for_epoch.remove(&epoch) {
+                self.epoch_for_hash.remove(&hash);
+                self.timestamp_for_epoch.remove(&epoch);
+                self.operator_set_for_epoch.remove(&epoch);
+                pruned += 1;
+            }
+        }
+
+        self.last_pruned_epoch = self.last_pruned_epoch.max(last_epoch);
+
+        let released_storage = initial_storage_usage.saturating_sub(env::storage_usage());
+
+        if released_storage > 0 {
+            Promise::new(env::predecessor_account_id())
+                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
+        }
+
+        pruned
+    }
+
+    /// `revoke_epoch` removes the operators of an epoch still within the key retention window, so
+    /// their proofs are rejected immediately, such as when their keys are compromised. Like pruned
+    /// operators, revoked operators can be registered again.
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch of the operators to revoke, the current operators must be replaced by
+    ///   transferring the operatorship first.
+    #[payable]
+    pub fn revoke_epoch(&mut self, epoch: u64) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.internal_revoke_epoch(epoch);
+    }
+
+    /// `force_set_epoch` is the break-glass recovery of an inconsistent state, it registers the
+    /// operators hash at the epoch and makes it the current epoch, without validating the operators.
+    /// It is only allowed while the contract is paused, so nothing is authorized until the state
+    /// has been checked.
+    ///
+    /// The hash previously registered at the epoch is unregistered, and the operator set of the
+    /// epoch is dropped as it is not known from the hash. Forcing a lower epoch drops the operators
+    /// of every epoch above it, up to the previous current epoch.
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch to make current.
+    /// * `hash`: The hash of the ABI encoded operators, weights and threshold.
+    #[payable]
+    pub fn force_set_epoch(&mut self, epoch: u64, hash: String) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.internal_force_set_epoch(epoch, hash);
+    }
+
+    /// Internal
+    /// It revokes the operators of an epoch that is not the current epoch
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch of the operators to revoke.
+    pub(crate) fn internal_revoke_epoch(&mut self, epoch: u64) {
+        if epoch == self.current_epoch {
+            AuthError::CurrentEpochRevocation.panic();
+        }
+
+        let hash = self
+            .hash_for_epoch
+            .remove(&epoch)
+            .unwrap_or_else(|| AuthError::UnknownEpoch.panic());
+
+        self.epoch_for_hash.remove(&hash);
+        self.timestamp_for_epoch.remove(&epoch);
+        self.operator_set_for_epoch.remove(&epoch);
+
+        KeySetRevokedEvent {
+            epoch,
+            operators_hash: utils::to_eth_hex_string(hash),
+        }
+        .emit();
+    }
+
+    /// It registers the operators hash at the epoch and makes it the current epoch, while the
+    /// contract is paused
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch to make current.
+    /// * `hash`: The hash of the ABI encoded operators, weights and threshold.
+    pub(crate) fn internal_force_set_epoch(&mut self, epoch: u64, hash: String) {
+        if !Self::is_paused() {
+            AuthError::NotPaused.panic();
+        }
+
+        if epoch == 0 {
+            AuthError::InvalidEpoch.panic();
+        }
+
+        let hash: [u8; 32] = try_clean_payload(hash)
+            .and_then(|hash| hash.try_into().ok())
+            .unwrap_or_else(|| env::panic_str("Invalid operators hash"));
+
+        let previous_epoch = self.current_epoch;
+
+        // No operators are left above the current epoch, the next transfer registers its operators
+        // at the epoch following it
+        for stale_epoch in epoch.saturating_add(1)..=previous_epoch {
+            if let Some(stale_hash) = self.hash_for_epoch.remove(&stale_epoch) {
+                self.epoch_for_hash.remove(&stale_hash);
+            }
+
+            self.timestamp_for_epoch.remove(&stale_epoch);
+            self.operator_set_for_epoch.remove(&stale_epoch);
+        }
+
+        if self
+            .epoch_for_hash
+            .get(&hash)
+            .is_some_and(|registered_epoch| registered_epoch != epoch)
+        {
+            AuthError::DuplicateOperators.panic();
+        }
+
+        if let Some(previous_hash) = self.hash_for_epoch.insert(&epoch, &hash) {
+            self.epoch_for_hash.remove(&previous_hash);
+        }
+
+        self.epoch_for_hash.insert(&hash, &epoch);
+        self.timestamp_for_epoch
+            .insert(&epoch, &env::block_timestamp());
+        self.operator_set_for_epoch.remove(&epoch);
+
+        self.current_epoch = epoch;
+        // The epochs registered again from here on must still be reachable by `prune_expired`
+        self.last_pruned_epoch = self.last_pruned_epoch.min(epoch - 1);
+
+        EmergencyStateSetEvent {
+            previous_epoch,
+            epoch,
+            operators_hash: utils::to_eth_hex_string(hash),
+        }
+        .emit();
+    }
+
+    /// It takes in a list of addresses and a list of weights, and if the list of addresses is sorted
+    /// and contains no duplicates, and if the list of weights is the same length as the list of
+    /// addresses, and if the sum of the weights is greater than the threshold, then it emits an event
+    ///
+    /// A threshold with `FRACTIONAL_THRESHOLD_FLAG` set is a fraction of the total weight in basis
+    /// points, such as 6667 for two thirds. It is rounded up to an absolute threshold, which the
+    /// operators are registered and must sign their proofs with.
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The parameters passed to the function.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` of the new operators, or an `AuthError` if the new operators, weights or
+    /// threshold are invalid.
+    pub(crate) fn internal_transfer_operatorship(
+        &mut self,
+        params: Vec<u8>,
+    ) -> Result<TransferResult, AuthError> {
+        self.internal_transfer_operatorship_of::<H160>(params)
+    }
+
+    /// It transfers the operatorship like `internal_transfer_operatorship`, to operators with keys of
+    /// type `K`. The operators are the public keys of Ed25519 operators, their params hash is
+    /// tagged with the key type, and only the operator sets of secp256k1 operators are stored.
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The ABI encoded operators, weights and threshold.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` of the new operators, or an `AuthError` if the new operators, weights or
+    /// threshold are invalid.
+    pub(crate) fn internal_transfer_operatorship_of<K: OperatorKey>(
+        &mut self,
+        params: Vec<u8>,
+    ) -> Result<TransferResult, AuthError> {
+        if Self::is_paused() {
+            return Err(AuthError::Paused);
+        }
+
+        let expected_output_types = vec![
+            ParamType::Array(Box::new(K::PARAM_TYPE)),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Uint(256),
+        ];
+
+        let tokens = abi_decode(&params, &expected_output_types)?;
+
+        let new_operators = tokens[0]
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|token| K::from_token(token).unwrap())
+            .collect::<Vec<_>>();
+
+        let new_weights = tokens[1]
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.into_uint().unwrap())
+            .collect::<Vec<_>>();
+
+        let new_threshold = tokens[2].clone().into_uint().unwrap();
+
+        let operators_length = new_operators.len();
+        let weights_length = new_weights.len();
+
+        if !Axelar::internal_is_sorted_asc_and_contains_no_duplicate(&new_operators) {
+            return Err(AuthError::InvalidOperators);
+        }
+
+        if !new_operators.iter().all(K::is_valid_operator) {
+            return Err(AuthError::InvalidOperators);
+        }
+
+        if operators_length < self.min_operators as usize {
+            return Err(AuthError::TooFewOperators);
+        }
+
+        if weights_length != operators_length {
+            return Err(AuthError::InvalidWeights);
+        }
+
+        // Weights and threshold are enforced as u32, larger values must not be truncated
+        let max_weight = U256::from(u32::MAX);
+
+        if new_weights.iter().any(|weight| *weight > max_weight) {
+            return Err(AuthError::InvalidWeights);
+        }
+
+        let is_fractional_threshold = new_threshold.bit(255);
+
+        if !is_fractional_threshold && new_threshold > max_weight {
+            return Err(AuthError::InvalidThreshold);
+        }
+
+        let mut total_weight: u64 = 0;
+
+        for weight in new_weights.iter() {
+            total_weight = total_weight
+                .checked_add(weight.as_u32().into())
+                .ok_or(AuthError::WeightOverflow)?;
+        }
+
+        // The fraction is resolved against the total weight of these operators, only the absolute
+        // threshold is registered
+        let new_threshold = if is_fractional_threshold {
+            let basis_points = new_threshold - FRACTIONAL_THRESHOLD_FLAG;
+
+            if basis_points.is_zero() || basis_points > U256::from(MAX_BASIS_POINTS) {
+                return Err(AuthError::InvalidThreshold);
+            }
+
+            let threshold =
+                utils::fractional_threshold(total_weight, basis_points.as_u64(), MAX_BASIS_POINTS);
+
+            if threshold > max_weight {
+                return Err(AuthError::InvalidThreshold);
+            }
+
+            threshold
+        } else {
+            new_threshold
+        };
+
+        if new_threshold.is_zero() || total_weight < new_threshold.as_u64() {
+            return Err(AuthError::InvalidThreshold);
+        }
+
+        // The proofs of the operators carry the absolute threshold
+        let new_operators_hash = if is_fractional_threshold {
+            utils::operators_hash(
+                K::KEY_TYPE,
+                &encode_operators(&new_operators, &new_weights, new_threshold),
+            )
+        } else {
+            utils::operators_hash(K::KEY_TYPE, &params)
+        };
+
+        let existing_epoch = self.epoch_for_hash.get(&new_operators_hash).unwrap_or(0);
+
+        if existing_epoch > 0 {
+            return Err(AuthError::DuplicateOperators);
+        }
+
+        let epoch = self.current_epoch + 1;
+        self.current_epoch = epoch;
+        self.hash_for_epoch.insert(&epoch, &new_operators_hash);
+        self.epoch_for_hash.insert(&new_operators_hash, &epoch);
+        self.timestamp_for_epoch
+            .insert(&epoch, &env::block_timestamp());
+
+        // The operator sets are stored as addresses, Ed25519 epochs are only known by their hash
+        if K::KEY_TYPE == KeyType::Secp256k1 {
+            self.operator_set_for_epoch.insert(
+                &epoch,
+                &OperatorSet {
+                    operators: new_operators
+                        .iter()
+                        .map(|operator| EthAddress(H160::from_slice(operator.as_ref())))
+                        .collect(),
+                    weights: new_weights.iter().copied().map(Weight).collect(),
+                    threshold: Weight(new_threshold),
+                },
+            );
+        }
+
+        // The operator sets are only kept within the key retention window, the hashes of the
+        // expired epochs are removed by `prune_expired`
+        if let Some(expired_epoch) = epoch.checked_sub(self.old_key_retention) {
+            self.operator_set_for_epoch.remove(&expired_epoch);
+        }
+
+        // Emit event
+        let event = OperatorshipTransferredEvent {
+            new_operators: new_operators
+                .iter()
+                .map(|x| format!("{:#x}", x))
+                .collect::<Vec<_>>(),
+            new_weights: new_weights.iter().map(|x| x.as_u64()).collect::<Vec<_>>(),
+            new_threshold: new_threshold.as_u64(),
+            epoch,
+        };
+
+        event.emit();
+
+        Ok(TransferResult {
+            epoch,
+            operators_hash: utils::to_eth_hex_string(new_operators_hash),
+        })
+    }
+
+    /// It validates the proof like `internal_validate_proof`, and emits a `ProofValidationFailedEvent`
+    /// when the proof is rejected, for the gateway path where the rejection fails the call
     ///
     /// Arguments:
     ///
-    /// * `epoch`: The epoch number for which you want to get the hash.
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The ABI encoded proof.
     ///
     /// Returns:
     ///
-    /// The hash of the block at the given epoch.
-    pub fn hash_for_epoch(&self, epoch: u64) -> String {
-        let hash = self.hash_for_epoch.get(&epoch).unwrap();
-        utils::to_eth_hex_string(hash)
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    fn internal_validate_proof_with_event(
+        &self,
+        message_hash: String,
+        proof: Vec<u8>,
+    ) -> Result<ValidatedProof, ProofError> {
+        self.internal_validate_proof(message_hash.clone(), proof)
+            .inspect_err(|error| Self::internal_emit_proof_failure(message_hash, error))
     }
 
-    /// `epoch_for_hash` returns the epoch number for a given hash
+    /// It turns the result of the validation of a proof into the result of `validate_proof`, a proof
+    /// whose signers are not the operators or do not reach the threshold is rejected with `false`.
+    /// The `ProofValidationFailedEvent` of the rejection is only emitted then, as the call returns
+    /// normally, the errors fail the call and are reported by it.
     ///
     /// Arguments:
     ///
-    /// * `hash`: The hash of the block to get the epoch for.
+    /// * `message_hash`: The hash of the message the proof was submitted for.
+    /// * `result`: The result of the validation.
     ///
     /// Returns:
     ///
-    /// The epoch for the hash.
-    pub fn epoch_for_hash(&self, hash: String) -> u64 {
-        let hash: [u8; 32] = clean_payload(hash).try_into().unwrap();
-        self.epoch_for_hash.get(&hash).unwrap()
+    /// Whether the proof was signed by the current operators, or the `ProofError` of a proof that
+    /// can not be validated.
+    fn internal_proof_result(
+        message_hash: String,
+        result: Result<ValidatedProof, ProofError>,
+    ) -> Result<bool, ProofError> {
+        match result {
+            Ok(proof) => Ok(proof.is_current_operators),
+            Err(
+                error @ ProofError::InvalidSignatures(
+                    SignatureError::LowSignatureWeight | SignatureError::MalformedSigners(_),
+                ),
+            ) => {
+                Self::internal_emit_proof_failure(message_hash, &error);
+                Ok(false)
+            }
+            Err(error) => Err(error),
+        }
     }
 
-    /// If the epoch of the operators is the same as the current epoch, and the epoch of the operators
-    /// is not too old, then validate the signatures
+    /// It emits a `ProofValidationFailedEvent` for a rejected proof
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message the proof was submitted for.
+    /// * `error`: The reason the proof was rejected.
+    fn internal_emit_proof_failure(message_hash: String, error: &ProofError) {
+        let event = ProofValidationFailedEvent {
+            message_hash,
+            reason: error.reason().to_string(),
+        };
+
+        event.emit();
+    }
+
+    /// It validates the proof the same way as `validate_proof`, without emitting an event when the
+    /// proof is rejected
+    ///
+    /// The only storage read required is the epoch of the operators hash, `current_epoch` and the
+    /// key retention are part of the contract state. The creation timestamp of the epoch is only read
+    /// when a maximum proof age is set.
     ///
     /// Arguments:
     ///
     /// * `message_hash`: The hash of the message to be signed.
-    /// * `proof`: The proof that is being validated.
+    /// * `proof`: The ABI encoded proof.
     ///
     /// Returns:
     ///
-    /// A boolean value.
-    pub fn validate_proof(&self, message_hash: String, proof: String) -> bool {
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    pub(crate) fn internal_validate_proof(
+        &self,
+        message_hash: String,
+        proof: Vec<u8>,
+    ) -> Result<ValidatedProof, ProofError> {
+        self.internal_validate_proof_of::<H160>(message_hash, proof)
+    }
+
+    /// It validates the proof like `internal_validate_proof`, signed by operators with keys of type
+    /// `K`
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The ABI encoded proof.
+    ///
+    /// Returns:
+    ///
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    fn internal_validate_proof_of<K: OperatorKey>(
+        &self,
+        message_hash: String,
+        proof: Vec<u8>,
+    ) -> Result<ValidatedProof, ProofError> {
         let expected_output_types = vec![
-            ParamType::Array(Box::new(ParamType::Address)),
+            ParamType::Array(Box::new(K::PARAM_TYPE)),
             ParamType::Array(Box::new(ParamType::Uint(256))),
             ParamType::Uint(256),
             ParamType::Array(Box::new(ParamType::Bytes)),
         ];
 
-        let payload = clean_payload(proof.clone());
-
-        let tokens = abi_decode(&payload, &expected_output_types).unwrap();
+        let tokens = abi_decode(&proof, &expected_output_types)?;
 
-        let (operators, weights, threshold, signatures) = (
-            tokens[0].clone().into_array().unwrap(),
-            tokens[1].clone().into_array().unwrap(),
-            tokens[2].clone().into_uint().unwrap(),
-            tokens[3].clone().into_array().unwrap(),
-        );
-
-        let encoded_operators = abi_encode(vec![
-            Token::Array(operators.clone()),
-            Token::Array(weights.clone()),
-            Token::Uint(threshold.clone()),
-        ]);
+        let operators_hash = utils::operators_hash_from_proof(K::KEY_TYPE, &proof)
+            .ok_or(ProofError::MalformedProof)?;
 
-        let operators_hash = keccak256(&encoded_operators);
-        let operators_epoch = self.epoch_for_hash.get(&operators_hash).unwrap();
-        let epoch = self.current_epoch;
+        let mut tokens = tokens.into_iter();
 
-        if operators_epoch == 0 || epoch - operators_epoch >= OLD_KEY_RETENTION.into() {
-            env::panic_str("Invalid epoch")
-        }
+        let operators = tokens
+            .next()
+            .unwrap()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|x| K::from_token(x).unwrap())
+            .collect::<Vec<_>>();
+        let weights = tokens
+            .next()
+            .unwrap()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|x| x.into_uint().unwrap())
+            .collect::<Vec<_>>();
+        let threshold = tokens.next().unwrap().into_uint().unwrap();
+        let signatures = tokens.next().unwrap().into_array().unwrap();
+        let operators_epoch = self.internal_operators_epoch(operators_hash)?;
 
         self.internal_validate_signatures(
             to_h256(message_hash),
-            operators
-                .clone()
-                .into_iter()
-                .map(|x| x.into_address().unwrap())
-                .collect(),
-            weights
-                .clone()
-                .into_iter()
-                .map(|x| x.into_uint().unwrap().as_u32())
-                .collect(),
+            operators,
+            weights.iter().map(|x| x.as_u32()).collect(),
             threshold.as_u32(),
-            signatures.clone(),
-        );
+            signatures,
+        )?;
 
-        operators_epoch == epoch
+        Ok(ValidatedProof {
+            is_current_operators: operators_epoch == self.current_epoch,
+            operators_epoch,
+        })
     }
 
-    /// Only owner
-
-    /// `transfer_operatorship` is a public function that requires the caller to be the owner, and then
-    /// calls the internal function `internal_transfer_operatorship`
+    /// It validates a proof given as the registered operators params and the signatures, the params
+    /// are hashed as they are and only decoded after their epoch is found valid
     ///
     /// Arguments:
     ///
-    /// * `params`: Vec<u8>
-    #[payable]
-    pub fn transfer_operatorship(&mut self, params: String) -> bool {
-        Self::require_owner();
-        self.internal_transfer_operatorship(clean_payload(params))
-    }
-
-    /// Internal
-
-    /// It takes in a list of addresses and a list of weights, and if the list of addresses is sorted
-    /// and contains no duplicates, and if the list of weights is the same length as the list of
-    /// addresses, and if the sum of the weights is greater than the threshold, then it emits an event
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `params`: The ABI encoded operators, weights and threshold.
+    /// * `signatures`: The signatures of the operators.
     ///
-    /// Arguments:
+    /// Returns:
     ///
-    /// * `params`: The parameters passed to the function.
-    pub(crate) fn internal_transfer_operatorship(&mut self, params: Vec<u8>) -> bool {
+    /// The `ValidatedProof` of the signing operators, or a `ProofError`.
+    fn internal_validate_proof_with_params(
+        &self,
+        message_hash: String,
+        params: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<ValidatedProof, ProofError> {
+        let operators_epoch = self.internal_operators_epoch(keccak256(&params))?;
+
         let expected_output_types = vec![
             ParamType::Array(Box::new(ParamType::Address)),
             ParamType::Array(Box::new(ParamType::Uint(256))),
             ParamType::Uint(256),
         ];
 
-        let tokens = abi_decode(&params, &expected_output_types).unwrap();
+        let mut tokens = abi_decode(&params, &expected_output_types)?.into_iter();
 
-        let new_operators = tokens[0]
-            .clone()
+        let operators = tokens
+            .next()
+            .unwrap()
             .into_array()
             .unwrap()
             .into_iter()
-            .map(|token| token.into_address().unwrap())
+            .map(|x| x.into_address().unwrap())
             .collect::<Vec<_>>();
-
-        let new_weights = tokens[1]
-            .clone()
+        let weights = tokens
+            .next()
+            .unwrap()
             .into_array()
             .unwrap()
             .into_iter()
-            .map(|token| token.into_uint().unwrap())
+            .map(|x| x.into_uint().unwrap().as_u32())
             .collect::<Vec<_>>();
+        let threshold = tokens.next().unwrap().into_uint().unwrap();
 
-        let new_threshold = tokens[2].clone().into_uint().unwrap();
-
-        let operators_length = new_operators.len();
-        let weights_length = new_weights.len();
-
-        if operators_length == 0
-            || !Axelar::internal_is_sorted_asc_and_contains_no_duplicate(new_operators.clone())
-        {
-            env::panic_str("Invalid operators");
-        }
+        self.internal_validate_signatures(
+            to_h256(message_hash),
+            operators,
+            weights,
+            threshold.as_u32(),
+            signatures.into_iter().map(Token::Bytes).collect(),
+        )?;
 
-        if weights_length != operators_length {
-            env::panic_str("Invalid weights");
-        }
+        Ok(ValidatedProof {
+            is_current_operators: operators_epoch == self.current_epoch,
+            operators_epoch,
+        })
+    }
 
-        let mut total_weight: u32 = 0;
+    /// It finds the epoch of the operators of a proof and checks that their proofs are still
+    /// accepted
+    ///
+    /// Arguments:
+    ///
+    /// * `operators_hash`: The hash of the operators params.
+    ///
+    /// Returns:
+    ///
+    /// The epoch of the operators, or a `ProofError` if they are unknown, out of the key retention
+    /// window or older than the maximum proof age.
+    fn internal_operators_epoch(&self, operators_hash: [u8; 32]) -> Result<u64, ProofError> {
+        let operators_epoch = self
+            .epoch_for_hash
+            .get(&operators_hash)
+            .ok_or(ProofError::UnknownOperators)?;
 
-        for i in 0..weights_length {
-            total_weight += new_weights[i].low_u32();
-        }
+        // An epoch above the current epoch is rejected rather than underflowing
+        let is_retained = self
+            .current_epoch
+            .checked_sub(operators_epoch)
+            .is_some_and(|age| age < self.old_key_retention);
 
-        if new_threshold.low_u32() == 0 || total_weight < new_threshold.low_u32() {
-            env::panic_str("Invalid threshold");
+        if operators_epoch == 0 || !is_retained {
+            return Err(ProofError::ExpiredEpoch);
         }
 
-        let new_operators_hash = keccak256(params);
-
-        let existing_epoch = self.epoch_for_hash.get(&new_operators_hash).unwrap_or(0);
-
-        if existing_epoch > 0 {
-            env::panic_str("Duplicate operators");
+        // Epochs created before their timestamp was recorded are only bound by the key retention
+        if let Some(max_proof_age) = self.max_proof_age {
+            if let Some(timestamp) = self.timestamp_for_epoch.get(&operators_epoch) {
+                if env::block_timestamp().saturating_sub(timestamp) > max_proof_age {
+                    return Err(ProofError::ExpiredProof);
+                }
+            }
         }
 
-        let epoch = self.current_epoch + 1;
-        self.current_epoch = epoch;
-        self.hash_for_epoch.insert(&epoch, &new_operators_hash);
-        self.epoch_for_hash.insert(&new_operators_hash, &epoch);
-
-        // Emit event
-        let event = OperatorshipTransferredEvent {
-            new_operators: new_operators
-                .iter()
-                .map(|x| format!("{:#x}", x))
-                .collect::<Vec<_>>(),
-            new_weights: new_weights.iter().map(|x| x.as_u64()).collect::<Vec<_>>(),
-            new_threshold: new_threshold.as_u64(),
-        };
-
-        event.emit();
-
-        true
+        Ok(operators_epoch)
     }
 
     /// It takes a list of operators, a list of weights, a threshold, and a list of signatures, and it
@@ -214,55 +1480,92 @@ impl Axelar {
     /// Arguments:
     ///
     /// * `message_hash`: The hash of the message to be signed.
-    /// * `operators`: The list of operators that are allowed to sign the transaction.
+    /// * `operators`: The list of operators that are allowed to sign the transaction, sorted in
+    ///   ascending order so the signers can be matched by binary search. The addresses of
+    ///   secp256k1 operators, or the public keys of Ed25519 operators.
     /// * `weights`: The weight of each operator.
     /// * `threshold`: The minimum number of signatures required to validate the transaction.
     /// * `signatures`: A list of signatures.
-    fn internal_validate_signatures(
+    ///
+    /// Returns:
+    ///
+    /// A `SignatureError` if there are more signatures than `max_signatures`, a signature is invalid
+    /// or the weight of the signers is less than the threshold.
+    fn internal_validate_signatures<K: OperatorKey>(
         &self,
         message_hash: H256,
-        operators: Vec<Address>,
+        operators: Vec<K>,
         weights: Vec<u32>,
         threshold: u32,
         signatures: Vec<Token>,
-    ) {
-        let operator_length = operators.len();
+    ) -> Result<(), SignatureError> {
+        // Rejected before recovering any signer, so the gas spent on a proof is bounded
+        if signatures.len() as u64 > self.max_signatures {
+            return Err(SignatureError::TooManySignatures);
+        }
+
         let mut operator_index = 0;
-        let mut weight = 0;
+        let mut weight: u64 = 0;
 
-        for i in 0..signatures.len() {
-            let signature: &[u8] = &signatures[i].clone().into_bytes().unwrap();
+        // The signers are only recovered until the threshold is reached, the trailing signatures
+        // were already decoded with the proof but are not recovered
+        for signature in signatures.into_iter() {
+            let signature = signature.into_bytes().unwrap();
 
-            let signer = utils::ecrecover(message_hash, signature).unwrap();
+            let signer = K::signer(message_hash, &signature)
+                .map_err(|_| SignatureError::InvalidSignature)?;
 
-            while operator_index < operator_length && operators[operator_index] != signer {
-                operator_index += 1;
-            }
+            // The position of the signer among the sorted operators, if it is one of them. The
+            // operator at that position is compared to the signer in constant time.
+            let find_signer = |operators: &[K]| -> Option<usize> {
+                let index = operators.partition_point(|operator| *operator < signer);
 
-            if operator_index >= operator_length {
-                env::panic_str(
-                    format!(
-                        "Malformed signers. Operators {}",
-                        operators
-                            .iter()
-                            .map(|x| format!("\"{}\"", x))
-                            .collect::<Vec<_>>()
-                            .join(",")
-                    )
-                    .as_str(),
-                );
-            }
+                operators
+                    .get(index)
+                    .filter(|operator| utils::ct_eq(operator.as_ref(), signer.as_ref()))
+                    .map(|_| index)
+            };
+
+            // The operators are sorted, so each signer is searched among the operators after the
+            // last matched one. Signers must strictly advance through them, so an operator is never
+            // counted twice.
+            let matched_index =
+                find_signer(&operators[operator_index..]).map(|offset| operator_index + offset);
 
-            weight += weights[operator_index];
+            #[cfg(feature = "verbose-logs")]
+            env::log_str(
+                &near_sdk::serde_json::json!({
+                    "signer": format!("{:#x}", signer),
+                    "operator_index": matched_index,
+                })
+                .to_string(),
+            );
 
-            if weight >= threshold {
-                return;
+            let matched_index = match matched_index {
+                Some(matched_index) => matched_index,
+                // An operator before the last matched one has signed twice or out of order
+                None if find_signer(&operators[..operator_index]).is_some() => {
+                    return Err(SignatureError::DuplicateOrUnorderedSigner)
+                }
+                None => {
+                    return Err(SignatureError::MalformedSigners(
+                        operators.iter().map(ToString::to_string).collect(),
+                    ))
+                }
+            };
+
+            weight = weight
+                .checked_add(weights[matched_index].into())
+                .ok_or(SignatureError::WeightOverflow)?;
+
+            if weight >= threshold.into() {
+                return Ok(());
             }
 
-            operator_index += 1;
+            operator_index = matched_index + 1;
         }
 
-        env::panic_str("Low signature weight");
+        Err(SignatureError::LowSignatureWeight)
     }
 
     /// > This function checks if the given vector of accounts is sorted in ascending order and contains
@@ -270,18 +1573,395 @@ impl Axelar {
     ///
     /// Arguments:
     ///
-    /// * `accounts`: A vector of H160, which is a type of vector of 20 bytes.
+    /// * `accounts`: A slice of H160, which is a type of vector of 20 bytes, or of the public keys
+    ///   of Ed25519 operators.
     ///
     /// Returns:
     ///
-    /// A boolean value.
-    fn internal_is_sorted_asc_and_contains_no_duplicate(accounts: Vec<H160>) -> bool {
-        for i in 0..(accounts.len() - 1) {
-            if accounts[i] >= accounts[i + 1] {
-                return false;
+    /// A boolean value, `false` for an empty slice or a zero first account. As the accounts are
+    /// sorted, no other account can be zero.
+    pub(crate) fn internal_is_sorted_asc_and_contains_no_duplicate<K: OperatorKey>(
+        accounts: &[K],
+    ) -> bool {
+        match accounts {
+            [] => false,
+            [first, ..] => {
+                first.as_ref().iter().any(|byte| *byte != 0)
+                    && accounts.windows(2).all(|pair| pair[0] < pair[1])
             }
         }
+    }
+}
+
+/// Test utilities, only compiled with the `test-utils` feature
+#[cfg(feature = "test-utils")]
+#[near_bindgen]
+impl Axelar {
+    /// `test_set_operators` registers an operators hash at an epoch and makes it the current epoch,
+    /// without the owner check and the validation of the operators
+    ///
+    /// Arguments:
+    ///
+    /// * `epoch`: The epoch to register the operators at.
+    /// * `hash`: The hash of the ABI encoded operators, weights and threshold.
+    pub fn test_set_operators(&mut self, epoch: u64, hash: String) {
+        let hash: [u8; 32] = clean_payload(hash)
+            .try_into()
+            .unwrap_or_else(|_| env::panic_str("Invalid operators hash"));
+
+        self.current_epoch = epoch;
+        self.hash_for_epoch.insert(&epoch, &hash);
+        self.epoch_for_hash.insert(&hash, &epoch);
+        self.timestamp_for_epoch
+            .insert(&epoch, &env::block_timestamp());
+    }
+
+    /// `test_validate_proof_recoveries` validates the proof the same way as `validate_proof`, and
+    /// counts the signers recovered to do so
+    ///
+    /// Arguments:
+    ///
+    /// * `message_hash`: The hash of the message to be signed.
+    /// * `proof`: The proof that is being validated.
+    ///
+    /// Returns:
+    ///
+    /// Whether the proof was accepted, and the number of signers recovered.
+    pub fn test_validate_proof_recoveries(
+        &self,
+        message_hash: String,
+        proof: String,
+    ) -> (bool, u64) {
+        let is_valid = self
+            .internal_validate_proof(message_hash, clean_payload(proof))
+            .is_ok();
+
+        (is_valid, utils::recovery_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    /// Small addresses so the generated operators often repeat or include the zero address, and
+    /// deduplicated sets so the accepted case is generated as well
+    fn operators() -> impl Strategy<Value = Vec<H160>> {
+        let address = (0u64..8).prop_map(H160::from_low_u64_be);
+
+        prop_oneof![
+            prop::collection::vec(address.clone(), 0..6),
+            prop::collection::btree_set(address, 0..6)
+                .prop_map(|operators| operators.into_iter().collect()),
+            prop::collection::vec(any::<[u8; 20]>().prop_map(H160::from), 0..4),
+        ]
+    }
+
+    proptest! {
+        // The operators are accepted if and only if they are strictly ascending, which is the order
+        // of the deduplicated `BTreeSet` of the same operators, and the first of them is not zero
+        #[test]
+        fn sorted_operators_match_the_btree_set_reference(operators in operators()) {
+            let sorted = operators.iter().copied().collect::<BTreeSet<_>>();
+            let expected = !operators.is_empty()
+                && !operators[0].is_zero()
+                && sorted.iter().eq(operators.iter());
+
+            prop_assert_eq!(
+                Axelar::internal_is_sorted_asc_and_contains_no_duplicate(&operators),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn empty_accounts_are_rejected() {
+        assert!(!Axelar::internal_is_sorted_asc_and_contains_no_duplicate::<
+            H160,
+        >(&[]));
+    }
+
+    #[test]
+    fn single_account_is_accepted_unless_zero() {
+        assert!(Axelar::internal_is_sorted_asc_and_contains_no_duplicate(&[
+            H160::from_low_u64_be(1)
+        ]));
+        assert!(!Axelar::internal_is_sorted_asc_and_contains_no_duplicate(
+            &[H160::zero()]
+        ));
+    }
+
+    #[test]
+    fn two_accounts_must_be_strictly_ascending_and_non_zero() {
+        let low = H160::from_low_u64_be(1);
+        let high = H160::from_low_u64_be(2);
+
+        assert!(Axelar::internal_is_sorted_asc_and_contains_no_duplicate(&[
+            low, high
+        ]));
+        assert!(!Axelar::internal_is_sorted_asc_and_contains_no_duplicate(
+            &[high, low]
+        ));
+        assert!(!Axelar::internal_is_sorted_asc_and_contains_no_duplicate(
+            &[low, low]
+        ));
+        assert!(!Axelar::internal_is_sorted_asc_and_contains_no_duplicate(
+            &[H160::zero(), high]
+        ));
+    }
+
+    const MESSAGE_HASH: [u8; 32] = [0x42; 32];
+
+    /// Three Ed25519 operators sorted by public key, each with a weight of 1 and a threshold of 2
+    fn ed25519_operators() -> Vec<Keypair> {
+        let mut keypairs = (1..=3u8)
+            .map(|seed| {
+                let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+                let public = PublicKey::from(&secret);
+
+                Keypair { secret, public }
+            })
+            .collect::<Vec<_>>();
+
+        keypairs.sort_by_key(|keypair| keypair.public.to_bytes());
+        keypairs
+    }
+
+    fn ed25519_params(keypairs: &[Keypair]) -> Vec<u8> {
+        let operators = keypairs
+            .iter()
+            .map(|keypair| H256(keypair.public.to_bytes()))
+            .collect::<Vec<_>>();
+
+        encode_operators(
+            &operators,
+            &vec![U256::one(); operators.len()],
+            U256::from(2),
+        )
+    }
+
+    /// A proof of the operators, signed by the `signers` over `message_hash`
+    fn ed25519_proof(
+        operators: &[Keypair],
+        signers: &[&Keypair],
+        message_hash: [u8; 32],
+    ) -> String {
+        let signatures = signers
+            .iter()
+            .map(|signer| {
+                let signature = signer.sign(&message_hash).to_bytes();
+
+                Token::Bytes([signer.public.to_bytes().as_slice(), &signature].concat())
+            })
+            .collect();
+
+        let proof = utils::abi_encode(vec![
+            Token::Array(
+                operators
+                    .iter()
+                    .map(|keypair| Token::FixedBytes(keypair.public.to_bytes().to_vec()))
+                    .collect(),
+            ),
+            Token::Array(vec![Token::Uint(U256::one()); operators.len()]),
+            Token::Uint(U256::from(2)),
+            Token::Array(signatures),
+        ]);
+
+        format!("0x{}", uint::hex::encode(proof))
+    }
+
+    fn ed25519_contract(operators: &[Keypair]) -> (Axelar, TransferResult) {
+        testing_env!(VMContextBuilder::new().build());
+
+        let mut contract = Axelar::new(vec![], None);
+        let result = contract
+            .internal_transfer_operatorship_of::<H256>(ed25519_params(operators))
+            .unwrap();
+
+        (contract, result)
+    }
+
+    #[test]
+    fn ed25519_operators_hash_is_tagged_with_the_key_type() {
+        let operators = ed25519_operators();
+        let params = ed25519_params(&operators);
+        let (_, result) = ed25519_contract(&operators);
+
+        assert_eq!(
+            result.operators_hash,
+            utils::to_eth_hex_string(utils::operators_hash(KeyType::Ed25519, &params))
+        );
+        assert_ne!(
+            result.operators_hash,
+            utils::to_eth_hex_string(keccak256(&params))
+        );
+        assert_eq!(
+            utils::operators_hash(KeyType::Secp256k1, &params),
+            keccak256(&params)
+        );
+    }
+
+    #[test]
+    fn ed25519_operators_validate_a_proof() {
+        let operators = ed25519_operators();
+        let (contract, _) = ed25519_contract(&operators);
+        let proof = ed25519_proof(&operators, &[&operators[0], &operators[2]], MESSAGE_HASH);
+
+        assert_eq!(
+            contract.validate_proof_ed25519(utils::to_eth_hex_string(MESSAGE_HASH), proof.clone()),
+            Ok(true)
+        );
+
+        // The hash of the operators is tagged, so the proof is not one of secp256k1 operators
+        assert_eq!(
+            contract.validate_proof(utils::to_eth_hex_string(MESSAGE_HASH), proof),
+            Err(ProofError::UnknownOperators)
+        );
+    }
+
+    #[test]
+    fn ed25519_proof_below_the_threshold_is_rejected() {
+        let operators = ed25519_operators();
+        let (contract, _) = ed25519_contract(&operators);
+        let proof = ed25519_proof(&operators, &[&operators[1]], MESSAGE_HASH);
+
+        assert_eq!(
+            contract.validate_proof_ed25519(utils::to_eth_hex_string(MESSAGE_HASH), proof),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn ed25519_signature_of_another_message_is_rejected() {
+        let operators = ed25519_operators();
+        let (contract, _) = ed25519_contract(&operators);
+        let proof = ed25519_proof(&operators, &[&operators[0], &operators[1]], [0x24; 32]);
+
+        assert_eq!(
+            contract.validate_proof_ed25519(utils::to_eth_hex_string(MESSAGE_HASH), proof),
+            Err(ProofError::InvalidSignatures(
+                SignatureError::InvalidSignature
+            ))
+        );
+    }
+
+    #[test]
+    fn ed25519_signer_that_is_not_an_operator_is_rejected() {
+        let operators = ed25519_operators();
+        let (contract, _) = ed25519_contract(&operators[..2]);
+        let proof = ed25519_proof(
+            &operators[..2],
+            &[&operators[0], &operators[2]],
+            MESSAGE_HASH,
+        );
+
+        assert_eq!(
+            contract.validate_proof_ed25519(utils::to_eth_hex_string(MESSAGE_HASH), proof),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn under_weighted_proof_is_rejected_with_false() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let operators = [H160::from_low_u64_be(1)];
+        let params = encode_operators(&operators, &[U256::one()], U256::one());
+        let mut contract = Axelar::new(vec![], None);
+
+        contract.internal_transfer_operatorship(params).unwrap();
+
+        let proof = |operators: &[H160]| {
+            let proof = utils::abi_encode(vec![
+                Token::Array(operators.iter().copied().map(Token::Address).collect()),
+                Token::Array(vec![Token::Uint(U256::one()); operators.len()]),
+                Token::Uint(U256::one()),
+                Token::Array(vec![]),
+            ]);
+
+            format!("0x{}", uint::hex::encode(proof))
+        };
+
+        assert_eq!(
+            contract.validate_proof(utils::to_eth_hex_string(MESSAGE_HASH), proof(&operators)),
+            Ok(false)
+        );
+
+        // Only the proofs that can not be validated are errors
+        assert_eq!(
+            contract.validate_proof(
+                utils::to_eth_hex_string(MESSAGE_HASH),
+                proof(&[H160::from_low_u64_be(2)])
+            ),
+            Err(ProofError::UnknownOperators)
+        );
+        assert_eq!(
+            contract.validate_proof(utils::to_eth_hex_string(MESSAGE_HASH), "0x1234".to_string()),
+            Err(ProofError::MalformedProof)
+        );
+    }
+
+    #[test]
+    fn proof_failure_event_is_only_emitted_when_returning_false() {
+        let operators = ed25519_operators();
+        let (contract, _) = ed25519_contract(&operators);
+        let message_hash = utils::to_eth_hex_string(MESSAGE_HASH);
+        let failure_events = || {
+            near_sdk::test_utils::get_logs()
+                .into_iter()
+                .filter(|log| log.contains("proof_validation_failed_event"))
+                .collect::<Vec<_>>()
+        };
+
+        let proof = ed25519_proof(&operators, &[&operators[1]], MESSAGE_HASH);
+
+        assert_eq!(
+            contract.validate_proof_ed25519(message_hash.clone(), proof),
+            Ok(false)
+        );
+        assert_eq!(failure_events().len(), 1);
+        assert!(failure_events()[0].contains("invalid_signatures"));
+
+        // The errors fail the call, which reports them
+        let proof = ed25519_proof(&operators[..2], &[&operators[0]], MESSAGE_HASH);
+
+        assert_eq!(
+            contract.validate_proof_ed25519(message_hash, proof),
+            Err(ProofError::UnknownOperators)
+        );
+        assert_eq!(failure_events().len(), 1);
+    }
+
+    #[test]
+    fn validate_signatures_rejects_malformed_hex_without_panicking() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let contract = Axelar::new(vec![], None);
+        let validate_signatures = |message_hash: &str, signature: &str| {
+            contract.validate_signatures(
+                message_hash.to_string(),
+                vec![EthAddress(H160::from_low_u64_be(1))],
+                vec![1],
+                1,
+                vec![signature.to_string()],
+            )
+        };
+        let signature = format!("0x{}", "11".repeat(65));
 
-        return !accounts[0].is_zero();
+        assert!(!validate_signatures("0xzz", &signature));
+        assert!(!validate_signatures("0x1234", &signature));
+        assert!(!validate_signatures(
+            &utils::to_eth_hex_string(MESSAGE_HASH),
+            "0xzz"
+        ));
+        assert!(!validate_signatures(
+            &utils::to_eth_hex_string(MESSAGE_HASH),
+            "1234"
+        ));
     }
 }
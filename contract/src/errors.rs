@@ -0,0 +1,215 @@
+/*
+ * Axelar errors
+ *
+ */
+use near_sdk::FunctionError;
+use std::fmt;
+
+/// `ProofError` is returned when a proof can not be accepted by `validate_proof`.
+///
+/// Variants:
+///
+/// * `MalformedProof`: The proof is not a valid ABI encoding of the operators and signatures.
+/// * `UnknownOperators`: The operators hash of the proof was never registered.
+/// * `ExpiredEpoch`: The operators epoch is older than the key retention window.
+/// * `ExpiredProof`: The operators epoch was created longer than the maximum proof age ago.
+/// * `InvalidSignatures`: The signatures do not validate against the operators.
+#[derive(FunctionError, Debug, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    MalformedProof,
+    UnknownOperators,
+    ExpiredEpoch,
+    ExpiredProof,
+    InvalidSignatures(SignatureError),
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofError::MalformedProof => write!(f, "Malformed proof"),
+            ProofError::UnknownOperators => write!(f, "Unknown operators"),
+            ProofError::ExpiredEpoch => write!(f, "Invalid epoch"),
+            ProofError::ExpiredProof => write!(f, "Expired proof"),
+            ProofError::InvalidSignatures(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl ProofError {
+    /// `reason` returns the code of the error, as reported in `ProofValidationFailedEvent`
+    ///
+    /// Returns:
+    ///
+    /// The snake case name of the variant.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            ProofError::MalformedProof => "malformed_proof",
+            ProofError::UnknownOperators => "unknown_operators",
+            ProofError::ExpiredEpoch => "expired_epoch",
+            ProofError::ExpiredProof => "expired_proof",
+            ProofError::InvalidSignatures(_) => "invalid_signatures",
+        }
+    }
+}
+
+impl From<ethabi::Error> for ProofError {
+    fn from(_: ethabi::Error) -> Self {
+        ProofError::MalformedProof
+    }
+}
+
+impl From<SignatureError> for ProofError {
+    fn from(error: SignatureError) -> Self {
+        ProofError::InvalidSignatures(error)
+    }
+}
+
+/// `AuthError` is returned when an operatorship transfer can not be applied.
+///
+/// Variants:
+///
+/// * `MalformedParams`: The params are not a hex string.
+/// * `InvalidAbiEncoding`: The params are not a valid ABI encoding of operators, weights and
+///   threshold, with the reason reported by the decoder.
+/// * `InvalidOperators`: The operators are empty, unsorted, duplicated or contain the zero address.
+/// * `TooFewOperators`: The number of operators is less than the minimum of the contract.
+/// * `InvalidWeights`: The number of weights does not match the number of operators, or a weight
+///   does not fit in 32 bits.
+/// * `InvalidThreshold`: The threshold is zero, does not fit in 32 bits, or is greater than the
+///   total weight. A fractional threshold is invalid outside of 1 to 10000 basis points.
+/// * `DuplicateOperators`: The operators were already registered for an epoch.
+/// * `WeightOverflow`: The total weight of the operators does not fit in 64 bits.
+/// * `Paused`: The operatorship transfers are paused.
+/// * `NotCurrentOperators`: The proof authorizing the transfer is not from the current operators.
+/// * `UnknownEpoch`: The epoch to revoke has no operators.
+/// * `CurrentEpochRevocation`: The current operators can only be revoked by transferring the
+///   operatorship.
+/// * `InvalidEpoch`: The epoch zero can not hold operators.
+/// * `NotPaused`: The current epoch can only be forced while the contract is paused.
+/// * `InvalidKeyRetention`: The key retention is zero, which would reject the current operators.
+#[derive(FunctionError, Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    MalformedParams,
+    InvalidAbiEncoding(String),
+    InvalidOperators,
+    TooFewOperators,
+    InvalidWeights,
+    InvalidThreshold,
+    DuplicateOperators,
+    WeightOverflow,
+    Paused,
+    NotCurrentOperators,
+    UnknownEpoch,
+    CurrentEpochRevocation,
+    InvalidEpoch,
+    NotPaused,
+    InvalidKeyRetention,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MalformedParams => write!(f, "Malformed operators params"),
+            AuthError::InvalidAbiEncoding(reason) => {
+                write!(f, "Malformed operators params: {}", reason)
+            }
+            AuthError::InvalidOperators => write!(f, "Invalid operators"),
+            AuthError::TooFewOperators => write!(f, "Too few operators"),
+            AuthError::InvalidWeights => write!(f, "Invalid weights"),
+            AuthError::InvalidThreshold => write!(f, "Invalid threshold"),
+            AuthError::DuplicateOperators => write!(f, "Duplicate operators"),
+            AuthError::WeightOverflow => write!(f, "Weight overflow"),
+            AuthError::Paused => write!(f, "Contract is paused"),
+            AuthError::NotCurrentOperators => write!(f, "Not the current operators"),
+            AuthError::UnknownEpoch => write!(f, "Unknown epoch"),
+            AuthError::CurrentEpochRevocation => write!(f, "Can not revoke the current epoch"),
+            AuthError::InvalidEpoch => write!(f, "Invalid epoch"),
+            AuthError::NotPaused => write!(f, "Contract is not paused"),
+            AuthError::InvalidKeyRetention => write!(f, "Invalid key retention"),
+        }
+    }
+}
+
+impl From<ethabi::Error> for AuthError {
+    fn from(error: ethabi::Error) -> Self {
+        let reason = match error {
+            ethabi::Error::InvalidName(name) => format!("Invalid name {}", name),
+            ethabi::Error::InvalidData => "Invalid data".to_string(),
+            ethabi::Error::Other(reason) => reason.into_owned(),
+        };
+
+        AuthError::InvalidAbiEncoding(reason)
+    }
+}
+
+/// `TimelockError` is returned when a time-locked owner operation can not be scheduled or
+/// executed.
+///
+/// Variants:
+///
+/// * `TimeLocked`: The operation is time-locked, it has to be scheduled and executed.
+/// * `AlreadyScheduled`: The operation is already scheduled.
+/// * `NotScheduled`: The operation is not scheduled, or was already executed or cancelled.
+/// * `NotReady`: The time-lock of the scheduled operation has not elapsed yet.
+#[derive(FunctionError, Debug, Clone, PartialEq, Eq)]
+pub enum TimelockError {
+    TimeLocked,
+    AlreadyScheduled,
+    NotScheduled,
+    NotReady,
+}
+
+impl fmt::Display for TimelockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimelockError::TimeLocked => write!(f, "Operation is time-locked"),
+            TimelockError::AlreadyScheduled => write!(f, "Operation already scheduled"),
+            TimelockError::NotScheduled => write!(f, "Operation not scheduled"),
+            TimelockError::NotReady => write!(f, "Operation is not ready"),
+        }
+    }
+}
+
+/// `SignatureError` is returned when the signatures of a proof do not validate against its operators.
+///
+/// Variants:
+///
+/// * `TooManySignatures`: The proof carries more signatures than the maximum, none of them are
+///   recovered.
+/// * `InvalidSignature`: A signature could not be recovered.
+/// * `MalformedSigners`: A signer is not one of the operators, which are given as displayed.
+/// * `DuplicateOrUnorderedSigner`: A signer signed twice, or the signers are not in operator order.
+/// * `WeightOverflow`: The weight of the signers does not fit in 64 bits.
+/// * `LowSignatureWeight`: The weight of the signers is less than the threshold.
+#[derive(FunctionError, Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    TooManySignatures,
+    InvalidSignature,
+    MalformedSigners(Vec<String>),
+    DuplicateOrUnorderedSigner,
+    WeightOverflow,
+    LowSignatureWeight,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::TooManySignatures => write!(f, "Too many signatures"),
+            SignatureError::InvalidSignature => write!(f, "Invalid signature"),
+            SignatureError::MalformedSigners(operators) => write!(
+                f,
+                "Malformed signers. Operators {}",
+                operators
+                    .iter()
+                    .map(|x| format!("\"{}\"", x))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            SignatureError::DuplicateOrUnorderedSigner => {
+                write!(f, "Duplicate or unordered signer")
+            }
+            SignatureError::WeightOverflow => write!(f, "Weight overflow"),
+            SignatureError::LowSignatureWeight => write!(f, "Low signature weight"),
+        }
+    }
+}
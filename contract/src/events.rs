@@ -1,69 +1,263 @@
 use near_contract_tools::event;
+use near_sdk::json_types::U128;
 
-/// Auth Events
-
-/// `OperatorshipTransferredEvent` is emitted when the operatorship is transferred.
-///
-/// Properties:
-///
-/// * `new_operators`: The new list of operators.
-/// * `new_weights`: A comma-separated list of weights for the new operators.
-/// * `new_threshold`: The new threshold for the operatorship.
-#[event(standard = "axelar_near", version = "1.0.0")]
-pub struct OperatorshipTransferredEvent {
-    pub new_operators: Vec<String>,
-    pub new_weights: Vec<u64>,
-    pub new_threshold: u64,
-}
-
-/// Gateway Events
-
-/// `ContractCallEvent` is emitted when a contract call is made to the gateway.
-///
-/// Properties:
-///
-/// * `address`: The address of the contract that emitted the event.
-/// * `destination_chain`: The chain that the contract call is being made to.
-/// * `destination_contract_address`: The address of the contract that will receive the call.
-/// * `payload_hash`: The hash of the payload.
-/// * `payload`: The payload of the contract call.
-#[event(standard = "axelar_near", version = "1.0.0")]
-pub struct ContractCallEvent {
-    pub address: String,
-    pub destination_chain: String,
-    pub destination_contract_address: String,
-    pub payload_hash: String,
-    pub payload: String,
-}
-
-/// `ExecutedEvent` is emitted when a contract call is executed.
-///
-/// Properties:
-///
-/// * `command_id`: The command ID that was executed.
-#[event(standard = "axelar_near", version = "1.0.0")]
-pub struct ExecutedEvent {
-    pub command_id: String,
-}
-
-/// `ContractCallApprovedEvent` is emitted when a contract call is approved.
-///
-/// Properties:
-///
-/// * `command_id`: The command ID of the command that was approved.
-/// * `source_chain`: The chain that the contract call originated from.
-/// * `source_address`: The address of the contract that is calling the target contract.
-/// * `contract_address`: The address of the contract that was called.
-/// * `payload_hash`: The hash of the payload that was sent to the contract.
-/// * `source_tx_hash`: The hash of the transaction that triggered the event.
-/// * `source_event_index`: The index of the event in the source chain.
-#[event(standard = "axelar_near", version = "1.0.0")]
-pub struct ContractCallApprovedEvent {
-    pub command_id: String,
-    pub source_chain: String,
-    pub source_address: String,
-    pub contract_address: String,
-    pub payload_hash: String,
-    pub source_tx_hash: String,
-    pub source_event_index: u64,
+/// `axelar_event` declares an event with the NEP-297 standard and version shared by all the events
+/// of the contract, so they are bumped in one place
+macro_rules! axelar_event {
+    ($item:item) => {
+        #[event(standard = "axelar_near", version = "1.1.0")]
+        $item
+    };
+}
+
+// Auth Events
+
+axelar_event! {
+    /// `OperatorshipTransferredEvent` is emitted when the operatorship is transferred.
+    ///
+    /// Properties:
+    ///
+    /// * `new_operators`: The new list of operators.
+    /// * `new_weights`: A comma-separated list of weights for the new operators.
+    /// * `new_threshold`: The new threshold for the operatorship.
+    /// * `epoch`: The epoch assigned to the new operators.
+    pub struct OperatorshipTransferredEvent {
+        pub new_operators: Vec<String>,
+        pub new_weights: Vec<u64>,
+        pub new_threshold: u64,
+        pub epoch: u64,
+    }
+}
+
+axelar_event! {
+    /// `KeySetRevokedEvent` is emitted when the operators of an epoch are revoked.
+    ///
+    /// Properties:
+    ///
+    /// * `epoch`: The epoch of the revoked operators.
+    /// * `operators_hash`: The hash of the revoked operators.
+    pub struct KeySetRevokedEvent {
+        pub epoch: u64,
+        pub operators_hash: String,
+    }
+}
+
+axelar_event! {
+    /// `EmergencyStateSetEvent` is emitted when the owner forces the current epoch during a
+    /// recovery.
+    ///
+    /// Properties:
+    ///
+    /// * `previous_epoch`: The current epoch before it was forced.
+    /// * `epoch`: The new current epoch.
+    /// * `operators_hash`: The hash of the operators registered for the new current epoch.
+    pub struct EmergencyStateSetEvent {
+        pub previous_epoch: u64,
+        pub epoch: u64,
+        pub operators_hash: String,
+    }
+}
+
+axelar_event! {
+    /// `ProofValidatedEvent` is emitted when a proof is accepted by `validate_proof_logged`.
+    ///
+    /// Properties:
+    ///
+    /// * `message_hash`: The hash of the message the proof was submitted for.
+    /// * `operators_epoch`: The epoch of the operators that signed the proof.
+    /// * `is_current`: Whether the proof was signed by the current operators.
+    pub struct ProofValidatedEvent {
+        pub message_hash: String,
+        pub operators_epoch: u64,
+        pub is_current: bool,
+    }
+}
+
+axelar_event! {
+    /// `ProofValidationFailedEvent` is emitted when a proof is rejected, by `validate_proof` and its
+    /// variants when they return `false` for a proof its operators did not sign enough, and by
+    /// `validate_proof_logged` when it rejects the proof of a command batch. The latter fails its
+    /// receipt, so the event of a rejected batch is only visible in the outcome of the gateway
+    /// `execute` transaction.
+    ///
+    /// Properties:
+    ///
+    /// * `message_hash`: The hash of the message the proof was submitted for.
+    /// * `reason`: The code of the error, one of `malformed_proof`, `unknown_operators`,
+    ///   `expired_epoch`, `expired_proof` or `invalid_signatures`.
+    pub struct ProofValidationFailedEvent {
+        pub message_hash: String,
+        pub reason: String,
+    }
+}
+
+axelar_event! {
+    /// `OwnershipTransferredEvent` is emitted when the owner of the contract changes.
+    ///
+    /// Properties:
+    ///
+    /// * `previous_owner`: The account of the previous owner.
+    /// * `new_owner`: The account of the new owner, `None` if the ownership was renounced.
+    pub struct OwnershipTransferredEvent {
+        pub previous_owner: Option<String>,
+        pub new_owner: Option<String>,
+    }
+}
+
+// Timelock Events
+
+axelar_event! {
+    /// `OperationScheduledEvent` is emitted when a time-locked owner operation is scheduled.
+    ///
+    /// Properties:
+    ///
+    /// * `operation_hash`: The hash of the scheduled operation.
+    /// * `ready_at`: The block timestamp in nanoseconds from which the operation can be executed.
+    pub struct OperationScheduledEvent {
+        pub operation_hash: String,
+        pub ready_at: u64,
+    }
+}
+
+axelar_event! {
+    /// `OperationExecutedEvent` is emitted when a scheduled operation is executed.
+    ///
+    /// Properties:
+    ///
+    /// * `operation_hash`: The hash of the executed operation.
+    pub struct OperationExecutedEvent {
+        pub operation_hash: String,
+    }
+}
+
+axelar_event! {
+    /// `OperationCancelledEvent` is emitted when a scheduled operation is cancelled.
+    ///
+    /// Properties:
+    ///
+    /// * `operation_hash`: The hash of the cancelled operation.
+    pub struct OperationCancelledEvent {
+        pub operation_hash: String,
+    }
+}
+
+// Gateway Events
+
+axelar_event! {
+    /// `ContractCallEvent` is emitted when a contract call is made to the gateway.
+    ///
+    /// Properties:
+    ///
+    /// * `address`: The address of the contract that emitted the event.
+    /// * `destination_chain`: The chain that the contract call is being made to.
+    /// * `destination_contract_address`: The address of the contract that will receive the call.
+    /// * `payload_hash`: The hash of the payload.
+    /// * `payload`: The payload of the contract call.
+    pub struct ContractCallEvent {
+        pub address: String,
+        pub destination_chain: String,
+        pub destination_contract_address: String,
+        pub payload_hash: String,
+        pub payload: String,
+    }
+}
+
+axelar_event! {
+    /// `ExecutedEvent` is emitted when a contract call is executed.
+    ///
+    /// Properties:
+    ///
+    /// * `command_id`: The command ID that was executed.
+    /// * `batch_index`: The position of the command in its batch.
+    /// * `operators_epoch`: The epoch of the operators that signed the batch of the command.
+    pub struct ExecutedEvent {
+        pub command_id: String,
+        pub batch_index: u64,
+        pub operators_epoch: u64,
+    }
+}
+
+axelar_event! {
+    /// `CommandSkippedEvent` is emitted when a command of a batch is not executed.
+    ///
+    /// Properties:
+    ///
+    /// * `command_id`: The command ID that was skipped.
+    /// * `batch_index`: The position of the command in its batch.
+    /// * `reason`: Why the command was skipped, one of `already_executed`, `unknown_command` or
+    ///   `operatorship_transfer_not_allowed`.
+    pub struct CommandSkippedEvent {
+        pub command_id: String,
+        pub batch_index: u64,
+        pub reason: String,
+    }
+}
+
+axelar_event! {
+    /// `CommandFailedEvent` is emitted when a command of a batch is executed without success, so it
+    /// can be executed again.
+    ///
+    /// Properties:
+    ///
+    /// * `command_id`: The command ID that failed.
+    /// * `batch_index`: The position of the command in its batch.
+    pub struct CommandFailedEvent {
+        pub command_id: String,
+        pub batch_index: u64,
+    }
+}
+
+axelar_event! {
+    /// `ContractCallApprovedEvent` is emitted when a contract call is approved.
+    ///
+    /// Properties:
+    ///
+    /// * `command_id`: The command ID of the command that was approved.
+    /// * `source_chain`: The chain that the contract call originated from.
+    /// * `source_address`: The address of the contract that is calling the target contract.
+    /// * `contract_address`: The address of the contract that was called.
+    /// * `payload_hash`: The hash of the payload that was sent to the contract.
+    /// * `source_tx_hash`: The hash of the transaction that triggered the event.
+    /// * `source_event_index`: The index of the event in the source chain.
+    pub struct ContractCallApprovedEvent {
+        pub command_id: String,
+        pub source_chain: String,
+        pub source_address: String,
+        pub contract_address: String,
+        pub payload_hash: String,
+        pub source_tx_hash: String,
+        pub source_event_index: u64,
+    }
+}
+
+axelar_event! {
+    /// `TokenDeployedEvent` is emitted when a token is registered by the `deployToken` command.
+    ///
+    /// Properties:
+    ///
+    /// * `symbol`: The symbol of the token.
+    /// * `token_address`: The account of the token contract.
+    pub struct TokenDeployedEvent {
+        pub symbol: String,
+        pub token_address: String,
+    }
+}
+
+axelar_event! {
+    /// `TokenSentEvent` is emitted when tokens are burnt or locked to be sent to another chain.
+    ///
+    /// Properties:
+    ///
+    /// * `sender`: The account that sent the tokens.
+    /// * `destination_chain`: The chain that the tokens are sent to.
+    /// * `destination_address`: The address that receives the tokens on the destination chain.
+    /// * `symbol`: The symbol of the token.
+    /// * `amount`: The amount of tokens sent.
+    pub struct TokenSentEvent {
+        pub sender: String,
+        pub destination_chain: String,
+        pub destination_address: String,
+        pub symbol: String,
+        pub amount: U128,
+    }
 }
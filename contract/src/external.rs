@@ -0,0 +1,29 @@
+use crate::auth_weighted::{TransferResult, ValidatedProof};
+use near_sdk::json_types::U128;
+use near_sdk::{ext_contract, AccountId};
+
+/// A trait that defines the functions of the token contracts deployed by the gateway.
+#[allow(dead_code)]
+#[ext_contract(axelar_token)]
+pub trait AxelarToken {
+    fn mint(&mut self, account_id: AccountId, amount: U128);
+
+    fn burn(&mut self, account_id: AccountId, amount: U128);
+}
+
+/// A trait that defines the functions of the auth module the gateway validates proofs with.
+#[allow(dead_code)]
+#[ext_contract(axelar_auth)]
+pub trait AxelarAuth {
+    fn validate_proof(&self, message_hash: String, proof: String) -> bool;
+
+    fn validate_proof_epoch(&self, message_hash: String, proof: String) -> ValidatedProof;
+
+    fn validate_proof_logged(&mut self, message_hash: String, proof: String) -> ValidatedProof;
+
+    fn epoch_for_hash(&self, hash: String) -> Option<u64>;
+
+    fn current_epoch(&self) -> u64;
+
+    fn transfer_operatorship(&mut self, params: String) -> TransferResult;
+}
@@ -0,0 +1,130 @@
+/*
+ * Axelar fuzzing entry points
+ *
+ */
+pub use crate::auth_weighted::DecodedProof;
+pub use crate::errors::{AuthError, ProofError, SignatureError};
+use crate::Axelar;
+pub use ethabi::ethereum_types::H160;
+use ethabi::ethereum_types::U256;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, MockedBlockchain, RuntimeFeesConfig, VMConfig};
+
+/// `contract` sets up a mocked blockchain with empty storage and initializes a contract with the
+/// given operators
+///
+/// Arguments:
+///
+/// * `recent_operators`: The ABI encoded operators, weights and threshold of each epoch.
+///
+/// Returns:
+///
+/// The contract, owned by the predecessor of the mocked context.
+pub fn contract(recent_operators: Vec<Vec<u8>>) -> Axelar {
+    env::set_blockchain_interface(MockedBlockchain::new(
+        VMContextBuilder::new().build(),
+        VMConfig::test(),
+        RuntimeFeesConfig::test(),
+        vec![],
+        Default::default(),
+        Default::default(),
+        None,
+    ));
+
+    let mut contract = Axelar::new(vec![], None);
+
+    for params in recent_operators {
+        contract
+            .internal_transfer_operatorship(params)
+            .unwrap_or_else(|error| panic!("Invalid fuzzing operators: {}", error));
+    }
+
+    contract
+}
+
+/// `validate_proof` validates raw proof bytes the same way as the `validate_proof` method
+///
+/// Arguments:
+///
+/// * `contract`: The contract to validate the proof with.
+/// * `message_hash`: The hash of the message to be signed.
+/// * `proof`: The ABI encoded proof.
+///
+/// Returns:
+///
+/// Whether the proof was signed by the current operators, or a `ProofError`.
+pub fn validate_proof(
+    contract: &Axelar,
+    message_hash: [u8; 32],
+    proof: &[u8],
+) -> Result<bool, ProofError> {
+    contract
+        .internal_validate_proof(
+            crate::utils::to_eth_hex_string(message_hash),
+            proof.to_vec(),
+        )
+        .map(|proof| proof.is_current_operators)
+}
+
+/// `decode_proof` decodes raw proof bytes the same way as the `decode_proof` view
+///
+/// Arguments:
+///
+/// * `contract`: The contract to decode the proof with.
+/// * `proof`: The ABI encoded proof.
+///
+/// Returns:
+///
+/// The `DecodedProof`, or `None` if the proof is malformed.
+pub fn decode_proof(contract: &Axelar, proof: &[u8]) -> Option<DecodedProof> {
+    contract.decode_proof(format!("0x{}", uint::hex::encode(proof)))
+}
+
+/// `transfer_operatorship` applies raw params bytes the same way as the `transfer_operatorship`
+/// method, returning the error instead of panicking
+///
+/// Arguments:
+///
+/// * `contract`: The contract to transfer the operatorship of.
+/// * `params`: The ABI encoded operators, weights and threshold.
+///
+/// Returns:
+///
+/// An `AuthError` if the params are rejected.
+pub fn transfer_operatorship(contract: &mut Axelar, params: &[u8]) -> Result<(), AuthError> {
+    contract
+        .internal_transfer_operatorship(params.to_vec())
+        .map(|_| ())
+}
+
+/// `is_sorted_asc_and_contains_no_duplicate` checks the operators the same way as the operatorship
+/// transfers do before registering them
+///
+/// Arguments:
+///
+/// * `operators`: The operator addresses.
+///
+/// Returns:
+///
+/// A boolean value, `false` for an empty slice or a zero first operator.
+pub fn is_sorted_asc_and_contains_no_duplicate(operators: &[H160]) -> bool {
+    Axelar::internal_is_sorted_asc_and_contains_no_duplicate(operators)
+}
+
+/// `transfer_operators` transfers the operatorship to the given operators, each with a weight of 1
+/// and a threshold of 1
+///
+/// Arguments:
+///
+/// * `contract`: The contract to transfer the operatorship of.
+/// * `operators`: The operator addresses.
+///
+/// Returns:
+///
+/// An `AuthError` if the operators are rejected.
+pub fn transfer_operators(contract: &mut Axelar, operators: &[H160]) -> Result<(), AuthError> {
+    let params =
+        crate::utils::encode_operators(operators, &vec![U256::one(); operators.len()], U256::one());
+
+    contract.internal_transfer_operatorship(params).map(|_| ())
+}
@@ -1,20 +1,108 @@
-use crate::events::{ContractCallApprovedEvent, ContractCallEvent, ExecutedEvent};
+use crate::auth_weighted::ValidatedProof;
+use crate::events::{
+    CommandFailedEvent, CommandSkippedEvent, ContractCallApprovedEvent, ContractCallEvent,
+    ExecutedEvent, TokenDeployedEvent, TokenSentEvent,
+};
+use crate::external::{axelar_auth, axelar_token};
 use crate::utils::{self, abi_encode, clean_payload};
 use crate::{utils::abi_decode, utils::keccak256, Axelar, AxelarExt};
+use ethabi::ethereum_types::U256;
 use ethabi::Token;
 use near_contract_tools::owner::*;
+use near_contract_tools::standard::nep141::ext_nep141;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSchema, BorshSerialize};
 use near_sdk::env::{self};
+use near_sdk::json_types::{Base64VecU8, U128};
 
 use ethabi::ParamType;
 use near_contract_tools::standard::nep297::Event;
 use near_sdk::env::predecessor_account_id;
-use near_sdk::{near_bindgen, AccountId};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::{
+    assert_one_yocto, is_promise_success, near_bindgen, AccountId, Balance, FunctionError, Gas,
+    Promise, PromiseError,
+};
 use uint::hex::{self};
 
 /// Defining a constant string called SELECTOR_APPROVE_CONTRACT_CALL.
 pub const SELECTOR_APPROVE_CONTRACT_CALL: &str = "approveContractCall";
 /// Defining a constant string called SELECTOR_TRANSFER_OPERATORSHIP.
 pub const SELECTOR_TRANSFER_OPERATORSHIP: &str = "transferOperatorship";
+/// Defining a constant string called SELECTOR_DEPLOY_TOKEN.
+pub const SELECTOR_DEPLOY_TOKEN: &str = "deployToken";
+/// Defining a constant string called SELECTOR_MINT_TOKEN.
+pub const SELECTOR_MINT_TOKEN: &str = "mintToken";
+
+/// One teragas, in gas units.
+pub const TGAS: u64 = 1_000_000_000_000;
+/// The gas attached to the initialization of a deployed token contract.
+pub const GAS_FOR_TOKEN_INIT: Gas = Gas(10 * TGAS);
+/// The gas attached to a mint or a transfer on a token contract.
+pub const GAS_FOR_TOKEN_TRANSFER: Gas = Gas(10 * TGAS);
+/// The gas attached to the proof validation of a command batch by the auth module by default.
+pub const GAS_FOR_VALIDATE_PROOF: Gas = Gas(10 * TGAS);
+/// The gas reserved for the callback executing the commands of a batch by default, on top of the
+/// share of the unused gas it receives.
+pub const GAS_FOR_EXECUTE_CALLBACK: Gas = Gas(10 * TGAS);
+/// The gas attached to an operatorship transfer on a separate auth module.
+pub const GAS_FOR_TRANSFER_OPERATORSHIP: Gas = Gas(10 * TGAS);
+/// The gas attached to each epoch lookup on the auth module when comparing the operators.
+pub const GAS_FOR_AUTH_EPOCH: Gas = Gas(5 * TGAS);
+/// The gas attached to the callback comparing the operators with the auth module.
+pub const GAS_FOR_OPERATORS_MATCH_CALLBACK: Gas = Gas(5 * TGAS);
+/// The gas attached to the callback of a token burn.
+pub const GAS_FOR_SEND_TOKEN_CALLBACK: Gas = Gas(5 * TGAS);
+/// The gas attached to the callback of the token deployment, the mint or the remote operatorship
+/// transfer of a command.
+pub const GAS_FOR_COMMAND_CALLBACK: Gas = Gas(5 * TGAS);
+/// The number of bytes of storage, on top of its code, a deployed token contract is funded for.
+pub const TOKEN_STORAGE_OVERHEAD: u64 = 10_000;
+
+/// `TokenType` tells how the gateway moves a token across chains.
+///
+/// Variants:
+///
+/// * `InternalBurnable`: The gateway deployed the token contract, it mints and burns the token.
+/// * `External`: The token contract was deployed by someone else, the gateway locks and releases it.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenType {
+    InternalBurnable,
+    External,
+}
+
+/// `SendTokenMessage` is the `msg` of an `ft_transfer_call` to the gateway, it tells where the
+/// transferred tokens are sent.
+///
+/// Properties:
+///
+/// * `destination_chain`: The chain that the tokens are sent to.
+/// * `destination_address`: The address that receives the tokens on the destination chain.
+/// * `symbol`: The symbol of the transferred token.
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SendTokenMessage {
+    pub destination_chain: String,
+    pub destination_address: String,
+    pub symbol: String,
+}
+
+/// `Command` is a command of a batch, carried from `execute` to `execute_callback`.
+///
+/// Properties:
+///
+/// * `command_id`: The hex encoded ID of the command.
+/// * `batch_index`: The position of the command in its batch, before the commands are selected.
+/// * `command`: The selector of the command.
+/// * `params`: The hex encoded ABI params of the command.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Command {
+    pub command_id: String,
+    pub batch_index: u64,
+    pub command: String,
+    pub params: String,
+}
 
 /// Axelar Gateway Implementation
 #[near_bindgen]
@@ -48,94 +136,249 @@ impl Axelar {
         event
     }
 
-    // Execute command function
+    /// `send_token` burns tokens deployed by the gateway from the caller, and emits a
+    /// `TokenSentEvent` event once they are burnt. External tokens are locked by transferring them
+    /// to the gateway with `ft_transfer_call` instead.
+    ///
+    /// Arguments:
+    ///
+    /// * `destination_chain`: The chain that the tokens are sent to.
+    /// * `destination_address`: The address that receives the tokens on the destination chain.
+    /// * `symbol`: The symbol of the token.
+    /// * `amount`: The amount of tokens to send.
+    #[payable]
+    pub fn send_token(
+        &mut self,
+        destination_chain: String,
+        destination_address: String,
+        symbol: String,
+        amount: U128,
+    ) -> Promise {
+        assert_one_yocto();
+
+        let token_account_id = self
+            .token_addresses
+            .get(&symbol)
+            .unwrap_or_else(|| env::panic_str("Token does not exist"));
+
+        if self.token_types.get(&symbol) != Some(TokenType::InternalBurnable) {
+            env::panic_str("External tokens are sent with ft_transfer_call");
+        }
+
+        let sender = predecessor_account_id();
 
-    /// It takes a message hash and a proof, validates the proof, and then executes the commands in the
-    /// message
+        axelar_token::ext(token_account_id)
+            .with_static_gas(GAS_FOR_TOKEN_TRANSFER)
+            .burn(sender.clone(), amount)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_SEND_TOKEN_CALLBACK)
+                    .send_token_callback(
+                        sender,
+                        destination_chain,
+                        destination_address,
+                        symbol,
+                        amount,
+                    ),
+            )
+    }
+
+    /// `send_token_callback` emits the `TokenSentEvent` event of a `send_token` once the tokens
+    /// are burnt
     ///
     /// Arguments:
     ///
-    /// * `message_hash`: The hash of the message that was signed by the operator.
-    /// * `input`: The input to the contract. This is the data that is passed to the contract.
+    /// * `sender`: The account that sent the tokens.
+    /// * `destination_chain`: The chain that the tokens are sent to.
+    /// * `destination_address`: The address that receives the tokens on the destination chain.
+    /// * `symbol`: The symbol of the token.
+    /// * `amount`: The amount of tokens sent.
+    /// * `burn_result`: The result of the burn on the token contract.
+    #[private]
+    pub fn send_token_callback(
+        &mut self,
+        sender: AccountId,
+        destination_chain: String,
+        destination_address: String,
+        symbol: String,
+        amount: U128,
+        #[callback_result] burn_result: Result<(), PromiseError>,
+    ) -> TokenSentEvent {
+        if burn_result.is_err() {
+            env::panic_str("Failed to burn the tokens");
+        }
+
+        let event = TokenSentEvent {
+            sender: sender.to_string(),
+            destination_chain,
+            destination_address,
+            symbol,
+            amount,
+        };
+
+        Event::emit(&event);
+
+        event
+    }
+
+    /// `ft_on_transfer` is called by a token contract when tokens are transferred to the gateway
+    /// with `ft_transfer_call`. External tokens are locked in the gateway and tokens deployed by
+    /// the gateway are burnt, then a `TokenSentEvent` event is emitted.
+    ///
+    /// Arguments:
+    ///
+    /// * `sender_id`: The account that transferred the tokens.
+    /// * `amount`: The amount of tokens transferred.
+    /// * `msg`: The JSON encoded `SendTokenMessage`.
     ///
     /// Returns:
     ///
-    /// The return value is a vector of booleans. Each boolean represents the result of the execution of
-    /// a command.
-    #[payable]
-    pub fn execute(&mut self, input: String) -> Vec<bool> {
-        let payload = clean_payload(input.clone());
+    /// The amount of tokens to refund, always 0 as the transfer is rejected otherwise.
+    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> U128 {
+        let message: SendTokenMessage = serde_json::from_str(&msg)
+            .unwrap_or_else(|_| env::panic_str("Invalid send token message"));
 
-        let tokens = abi_decode(&payload, &vec![ParamType::Bytes, ParamType::Bytes]).unwrap();
+        let token_account_id = predecessor_account_id();
 
-        let data = tokens[0].clone().into_bytes().unwrap();
-        let proof = tokens[1].clone().into_bytes().unwrap();
+        if self.token_addresses.get(&message.symbol) != Some(token_account_id.clone()) {
+            env::panic_str("Token does not exist");
+        }
 
-        let message = keccak256(data.clone());
-        const PREFIX: &str = "\x19Ethereum Signed Message:\n32";
-        let mut eth_message = PREFIX.as_bytes().to_vec();
-        eth_message.extend_from_slice(message.as_ref());
+        if self.token_types.get(&message.symbol) == Some(TokenType::InternalBurnable) {
+            axelar_token::ext(token_account_id)
+                .with_static_gas(GAS_FOR_TOKEN_TRANSFER)
+                .burn(env::current_account_id(), amount);
+        }
 
-        let hash_message = format!("0x{}", hex::encode(keccak256(eth_message)));
+        let event = TokenSentEvent {
+            sender: sender_id.to_string(),
+            destination_chain: message.destination_chain,
+            destination_address: message.destination_address,
+            symbol: message.symbol,
+            amount,
+        };
 
-        let mut allow_operatorship_transfer =
-            self.validate_proof(hash_message, format!("0x{}", hex::encode(proof)));
+        Event::emit(&event);
 
-        let expected_output_types = vec![
-            ParamType::Uint(256),
-            ParamType::Array(Box::new(ParamType::FixedBytes(32))),
-            ParamType::Array(Box::new(ParamType::String)),
-            ParamType::Array(Box::new(ParamType::Bytes)),
-        ];
+        U128(0)
+    }
 
-        let data_tokens = abi_decode(&data, &expected_output_types).unwrap();
+    // Execute command function
 
-        let chain_id = data_tokens[0].clone().into_uint().unwrap().as_u64();
-        let command_ids = data_tokens[1]
-            .clone()
-            .into_array()
-            .unwrap()
-            .into_iter()
-            .map(|token| token.into_fixed_bytes().unwrap())
-            .collect::<Vec<_>>();
+    /// It takes the command batch and its proof, decodes the commands, and validates the proof with
+    /// the auth module. The commands are executed by `execute_callback` once the proof is validated.
+    /// A batch executed again while its proof is validated does not execute its commands twice,
+    /// as each command is marked as executed before it makes any external call.
+    /// A batch too large for the gas of one transaction can be executed over several calls with the
+    /// same input, as the commands that were already executed are skipped.
+    ///
+    /// Arguments:
+    ///
+    /// * `input`: The ABI encoded command batch and proof.
+    /// * `command_selector`: The indices of the commands of the batch to execute in this call, all
+    ///   the commands if `None`.
+    ///
+    /// Returns:
+    ///
+    /// The promise of the proof validation, it resolves to the result of `execute_callback`.
+    #[payable]
+    pub fn execute(&mut self, input: String, command_selector: Option<Vec<usize>>) -> Promise {
+        // The proof validation and the callback must be funded up front, as an under-provisioned
+        // validation is indistinguishable from an invalid proof once it fails
+        if env::prepaid_gas() - env::used_gas() < self.validate_proof_gas + self.callback_gas {
+            env::panic_str("Not enough gas to validate the proof");
+        }
 
-        let commands = data_tokens[2]
-            .clone()
-            .into_array()
-            .unwrap()
-            .into_iter()
-            .map(|token| token.into_string().unwrap())
-            .collect::<Vec<_>>();
+        let payload = clean_payload(input);
 
-        let params = data_tokens[3]
-            .clone()
-            .into_array()
-            .unwrap()
-            .into_iter()
-            .map(|token| token.into_bytes().unwrap())
-            .collect::<Vec<_>>();
+        let tokens = abi_decode(&payload, &vec![ParamType::Bytes, ParamType::Bytes]).unwrap();
 
-        // TODO: Update to NEAR chain id which we need to decide on
-        if chain_id != 0 {
-            env::panic_str(format!("Invalid chain id: {}", chain_id).as_str());
-        }
+        let data = tokens[0].clone().into_bytes().unwrap();
+        let proof = tokens[1].clone().into_bytes().unwrap();
 
-        let commands_length = command_ids.len();
+        let hash_message =
+            utils::to_eth_hex_string(utils::to_eth_signed_message_hash(keccak256(&data)));
 
-        if commands_length != commands.len() || commands_length != params.len() {
-            env::panic_str("Invalid commands");
+        let mut commands = self.internal_decode_commands(data);
+
+        if let Some(command_selector) = command_selector {
+            if command_selector
+                .iter()
+                .any(|index| *index >= commands.len())
+            {
+                env::panic_str("Invalid command selector");
+            }
+
+            commands = commands
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| command_selector.contains(index))
+                .map(|(_, command)| command)
+                .collect();
         }
 
+        axelar_auth::ext(self.auth_module.clone())
+            .with_static_gas(self.validate_proof_gas)
+            .validate_proof_logged(hash_message, format!("0x{}", hex::encode(proof)))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(self.callback_gas)
+                    .execute_callback(commands),
+            )
+    }
+
+    /// It executes the commands of a batch once its proof was validated by the auth module, the
+    /// whole batch is rejected if the proof was not
+    ///
+    /// Arguments:
+    ///
+    /// * `commands`: The decoded commands of the batch.
+    /// * `validate_result`: The operators that signed the proof, or an error if the auth module
+    ///   rejected the proof.
+    ///
+    /// Returns:
+    ///
+    /// The return value is a vector of booleans. Each boolean represents the result of the execution of
+    /// a command.
+    #[private]
+    pub fn execute_callback(
+        &mut self,
+        commands: Vec<Command>,
+        #[callback_result] validate_result: Result<ValidatedProof, PromiseError>,
+    ) -> Vec<bool> {
+        let validated_proof = match validate_result {
+            Ok(validated_proof) => validated_proof,
+            Err(_) => env::panic_str("Invalid proof"),
+        };
+
+        let mut allow_operatorship_transfer = validated_proof.is_current_operators;
         let mut call_results: Vec<bool> = Vec::new();
 
-        for i in 0..commands_length {
-            let command_id: [u8; 32] = command_ids[i].clone().try_into().unwrap();
+        for Command {
+            command_id,
+            batch_index,
+            command,
+            params,
+        } in commands
+        {
+            let command_id: [u8; 32] = clean_payload(command_id).try_into().unwrap();
+
+            let skip = |reason: &str| {
+                let event = CommandSkippedEvent {
+                    command_id: utils::to_eth_hex_string(command_id),
+                    batch_index,
+                    reason: reason.to_string(),
+                };
 
-            if self.is_command_executed(format!("0x{}", hex::encode(command_id))) {
+                Event::emit(&event);
+            };
+
+            if self.is_command_executed(utils::to_eth_hex_string(command_id)) {
+                skip("already_executed");
                 continue;
             }
 
-            let command = commands[i].clone();
+            let params = clean_payload(params);
 
             let success: bool;
 
@@ -143,21 +386,51 @@ impl Axelar {
                 SELECTOR_APPROVE_CONTRACT_CALL => {
                     self.internal_set_command_executed(command_id, true);
                     success = self.internal_approve_contract_call(
-                        params[i].clone(),
+                        params,
                         utils::to_eth_hex_string(command_id),
                     );
                 }
+                SELECTOR_DEPLOY_TOKEN => {
+                    self.internal_set_command_executed(command_id, true);
+                    success = self.internal_deploy_token(params, command_id, batch_index);
+                }
+                SELECTOR_MINT_TOKEN => {
+                    self.internal_set_command_executed(command_id, true);
+                    success = self.internal_mint_token(params, command_id, batch_index);
+                }
                 SELECTOR_TRANSFER_OPERATORSHIP => {
                     if !allow_operatorship_transfer {
+                        skip("operatorship_transfer_not_allowed");
                         continue;
                     }
 
                     allow_operatorship_transfer = false;
                     self.internal_set_command_executed(command_id, true);
 
-                    success = self.internal_transfer_operatorship(params[i].clone());
+                    if self.auth_module == env::current_account_id() {
+                        self.internal_transfer_operatorship(params)
+                            .unwrap_or_else(|error| error.panic());
+                    } else {
+                        axelar_auth::ext(self.auth_module.clone())
+                            .with_static_gas(GAS_FOR_TRANSFER_OPERATORSHIP)
+                            .transfer_operatorship(format!("0x{}", hex::encode(&params)))
+                            .then(
+                                Self::ext(env::current_account_id())
+                                    .with_static_gas(GAS_FOR_COMMAND_CALLBACK)
+                                    .transfer_operatorship_callback(
+                                        utils::to_eth_hex_string(command_id),
+                                        batch_index,
+                                    ),
+                            );
+                    }
+
+                    success = true;
                 }
                 _ => {
+                    // Commands of a newer gateway version are consumed without effect, so they
+                    // are not replayed once this gateway is upgraded
+                    self.internal_set_command_executed(command_id, true);
+                    skip("unknown_command");
                     continue;
                 }
             };
@@ -165,11 +438,20 @@ impl Axelar {
             if success {
                 let event = ExecutedEvent {
                     command_id: utils::to_eth_hex_string(command_id),
+                    batch_index,
+                    operators_epoch: validated_proof.operators_epoch,
                 };
 
                 Event::emit(&event);
             } else {
                 self.internal_set_command_executed(command_id, false);
+
+                let event = CommandFailedEvent {
+                    command_id: utils::to_eth_hex_string(command_id),
+                    batch_index,
+                };
+
+                Event::emit(&event);
             }
 
             call_results.push(success);
@@ -178,6 +460,140 @@ impl Axelar {
         call_results
     }
 
+    /// `deploy_token_callback` rolls back a `deployToken` command whose token contract could not
+    /// be deployed, the token is unregistered and the command can be executed again
+    ///
+    /// Arguments:
+    ///
+    /// * `command_id`: The ID of the command.
+    /// * `batch_index`: The index of the command in its batch.
+    /// * `symbol`: The symbol of the deployed token.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the deployment failed.
+    #[private]
+    pub fn deploy_token_callback(
+        &mut self,
+        command_id: String,
+        batch_index: u64,
+        symbol: String,
+    ) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        self.token_addresses.remove(&symbol);
+        self.token_types.remove(&symbol);
+        self.token_mint_limits.remove(&symbol);
+
+        self.internal_rollback_command(command_id, batch_index);
+
+        false
+    }
+
+    /// `mint_token_callback` rolls back a `mintToken` command whose tokens could not be minted or
+    /// transferred, so the command can be executed again
+    ///
+    /// Arguments:
+    ///
+    /// * `command_id`: The ID of the command.
+    /// * `batch_index`: The index of the command in its batch.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the mint failed.
+    #[private]
+    pub fn mint_token_callback(&mut self, command_id: String, batch_index: u64) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        self.internal_rollback_command(command_id, batch_index);
+
+        false
+    }
+
+    /// `transfer_operatorship_callback` rolls back a `transferOperatorship` command that the
+    /// separate auth module rejected, such as an auth module that is paused, time-locked or not
+    /// owned by the gateway, so the command can be executed again
+    ///
+    /// Arguments:
+    ///
+    /// * `command_id`: The ID of the command.
+    /// * `batch_index`: The index of the command in its batch.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the transfer failed.
+    #[private]
+    pub fn transfer_operatorship_callback(&mut self, command_id: String, batch_index: u64) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        self.internal_rollback_command(command_id, batch_index);
+
+        false
+    }
+
+    /// `operators_hash_matches_auth` checks that the operators the gateway considers current are
+    /// also the current operators of the auth module, to catch the two contracts drifting apart
+    /// after a rotation. It is a call rather than a view, as it queries the auth module.
+    ///
+    /// Arguments:
+    ///
+    /// * `params`: The ABI encoded operators, weights and threshold.
+    ///
+    /// Returns:
+    ///
+    /// The promise of the epoch lookups on the auth module, it resolves to the result of
+    /// `operators_hash_matches_auth_callback`.
+    pub fn operators_hash_matches_auth(&self, params: String) -> Promise {
+        let is_current_operators = self.is_current_operators(params.clone());
+        let hash = utils::to_eth_hex_string(keccak256(clean_payload(params)));
+
+        axelar_auth::ext(self.auth_module.clone())
+            .with_static_gas(GAS_FOR_AUTH_EPOCH)
+            .epoch_for_hash(hash)
+            .and(
+                axelar_auth::ext(self.auth_module.clone())
+                    .with_static_gas(GAS_FOR_AUTH_EPOCH)
+                    .current_epoch(),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_OPERATORS_MATCH_CALLBACK)
+                    .operators_hash_matches_auth_callback(is_current_operators),
+            )
+    }
+
+    /// `operators_hash_matches_auth_callback` compares the epoch of the operators on the auth module
+    /// with its current epoch
+    ///
+    /// Arguments:
+    ///
+    /// * `is_current_operators`: Whether the operators are the current operators of the gateway.
+    /// * `epoch`: The epoch of the operators on the auth module.
+    /// * `current_epoch`: The current epoch of the auth module.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if either contract does not consider the operators current or the
+    /// auth module could not be queried.
+    #[private]
+    pub fn operators_hash_matches_auth_callback(
+        &self,
+        is_current_operators: bool,
+        #[callback_result] epoch: Result<Option<u64>, PromiseError>,
+        #[callback_result] current_epoch: Result<u64, PromiseError>,
+    ) -> bool {
+        match (epoch, current_epoch) {
+            (Ok(Some(epoch)), Ok(current_epoch)) => is_current_operators && epoch == current_epoch,
+            _ => false,
+        }
+    }
+
     /// Only Owner functions
 
     /// `approve_contract_call` is a function that is called by the `Bridge` contract on the source
@@ -197,6 +613,32 @@ impl Axelar {
         self.internal_approve_contract_call(payload, command_id)
     }
 
+    /// `set_auth_module` changes the contract that validates the proofs of the command batches. A
+    /// separate auth module must be owned by the gateway for `transferOperatorship` commands to
+    /// rotate its operators.
+    ///
+    /// Arguments:
+    ///
+    /// * `auth_module`: The account id of the auth module.
+    #[payable]
+    pub fn set_auth_module(&mut self, auth_module: AccountId) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.auth_module = auth_module;
+    }
+
+    /// `set_token_code` stores the code of the NEP-141 contract that the `deployToken` command
+    /// deploys, the gateway pays for its storage
+    ///
+    /// Arguments:
+    ///
+    /// * `code`: The base64 encoded wasm of the token contract.
+    #[payable]
+    pub fn set_token_code(&mut self, code: Base64VecU8) {
+        Self::require_owner();
+        self.token_code.set(&code.into());
+    }
+
     // View functions
 
     /// It returns a boolean value indicating whether a contract call has been approved
@@ -234,13 +676,54 @@ impl Axelar {
         self.bool_state.get(&key).unwrap_or(false)
     }
 
-    /// `auth_module` returns the account id of the current account
+    /// `auth_module` returns the account of the contract that validates the proofs of the command
+    /// batches
     ///
     /// Returns:
     ///
-    /// The account id of the current account.
+    /// The account id of the auth module.
     pub fn auth_module(&self) -> AccountId {
-        env::current_account_id()
+        self.auth_module.clone()
+    }
+
+    /// `validate_proof_gas` returns the gas attached to the proof validation of a command batch
+    ///
+    /// Returns:
+    ///
+    /// The gas of the call to the auth module.
+    pub fn validate_proof_gas(&self) -> Gas {
+        self.validate_proof_gas
+    }
+
+    /// `callback_gas` returns the gas reserved for the callback executing the commands of a batch
+    ///
+    /// Returns:
+    ///
+    /// The gas of the callback.
+    pub fn callback_gas(&self) -> Gas {
+        self.callback_gas
+    }
+
+    /// `chain_id` returns the chain id the command batches must be signed for
+    ///
+    /// Returns:
+    ///
+    /// The chain id.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// `token_address` returns the account of the token contract registered for a symbol
+    ///
+    /// Arguments:
+    ///
+    /// * `symbol`: The symbol of the token.
+    ///
+    /// Returns:
+    ///
+    /// The account of the token contract, or `None` if the symbol is unknown.
+    pub fn token_address(&self, symbol: String) -> Option<AccountId> {
+        self.token_addresses.get(&symbol)
     }
 
     /// `is_command_executed` returns `true` if the command with the given `command_id` has been
@@ -304,6 +787,74 @@ impl Axelar {
 
     // Internal functions
 
+    /// `internal_decode_commands` decodes a command batch signed for this gateway
+    ///
+    /// Arguments:
+    ///
+    /// * `data`: The ABI encoded chain id, command ids, commands and params.
+    ///
+    /// Returns:
+    ///
+    /// The commands of the batch, it panics if the batch is malformed or signed for another chain id.
+    fn internal_decode_commands(&self, data: Vec<u8>) -> Vec<Command> {
+        let expected_output_types = vec![
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+            ParamType::Array(Box::new(ParamType::String)),
+            ParamType::Array(Box::new(ParamType::Bytes)),
+        ];
+
+        let data_tokens = abi_decode(&data, &expected_output_types).unwrap();
+
+        let chain_id = data_tokens[0].clone().into_uint().unwrap().as_u64();
+        let command_ids = data_tokens[1]
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.into_fixed_bytes().unwrap())
+            .collect::<Vec<_>>();
+
+        let commands = data_tokens[2]
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.into_string().unwrap())
+            .collect::<Vec<_>>();
+
+        let params = data_tokens[3]
+            .clone()
+            .into_array()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.into_bytes().unwrap())
+            .collect::<Vec<_>>();
+
+        if chain_id != self.chain_id {
+            env::panic_str(format!("Invalid chain id: {}", chain_id).as_str());
+        }
+
+        let commands_length = command_ids.len();
+
+        if commands_length != commands.len() || commands_length != params.len() {
+            env::panic_str("Invalid commands");
+        }
+
+        command_ids
+            .into_iter()
+            .zip(commands)
+            .zip(params)
+            .enumerate()
+            .map(|(batch_index, ((command_id, command), params))| Command {
+                command_id: format!("0x{}", hex::encode(command_id)),
+                batch_index: batch_index as u64,
+                command,
+                params: format!("0x{}", hex::encode(params)),
+            })
+            .collect()
+    }
+
     /// `internal_approve_contract_call` is a function that is called by the `approve_contract_call`
     /// function in the `Bridge` contract
     ///
@@ -316,8 +867,6 @@ impl Axelar {
     ///
     /// A boolean value.
     fn internal_approve_contract_call(&mut self, payload: Vec<u8>, command_id: String) -> bool {
-        Self::require_owner();
-
         let expected_output_types = vec![
             ParamType::String,
             ParamType::String,
@@ -361,6 +910,196 @@ impl Axelar {
         true
     }
 
+    /// `internal_deploy_token` registers the token of a `deployToken` command. If the command has
+    /// no token address a new token contract is deployed to the `<symbol>.<gateway>` subaccount,
+    /// otherwise the existing contract at that account is registered as an external token.
+    ///
+    /// Arguments:
+    ///
+    /// * `payload`: The ABI encoded name, symbol, decimals, cap, token address and mint limit.
+    /// * `command_id`: The ID of the command, executed again if the deployment fails.
+    /// * `batch_index`: The index of the command in its batch.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the params are invalid or the symbol is already registered.
+    fn internal_deploy_token(
+        &mut self,
+        payload: Vec<u8>,
+        command_id: [u8; 32],
+        batch_index: u64,
+    ) -> bool {
+        let expected_output_types = vec![
+            ParamType::String,
+            ParamType::String,
+            ParamType::Uint(8),
+            ParamType::Uint(256),
+            ParamType::String,
+            ParamType::Uint(256),
+        ];
+
+        let tokens = match abi_decode(&payload, &expected_output_types) {
+            Ok(tokens) => tokens,
+            Err(_) => return false,
+        };
+
+        let name = tokens[0].clone().into_string().unwrap();
+        let symbol = tokens[1].clone().into_string().unwrap();
+        let decimals = tokens[2].clone().into_uint().unwrap().as_u32() as u8;
+        let cap = tokens[3].clone().into_uint().unwrap();
+        let token_address = tokens[4].clone().into_string().unwrap();
+        let mint_limit = tokens[5].clone().into_uint().unwrap();
+
+        let max_amount = U256::from(Balance::MAX);
+
+        if cap > max_amount || mint_limit > max_amount {
+            return false;
+        }
+
+        if self.token_addresses.contains_key(&symbol) {
+            return false;
+        }
+
+        let (token_account_id, token_type) = if token_address.is_empty() {
+            let token_account_id: AccountId =
+                match format!("{}.{}", symbol.to_lowercase(), env::current_account_id()).parse() {
+                    Ok(account_id) => account_id,
+                    Err(_) => return false,
+                };
+
+            let code = match self.token_code.get() {
+                Some(code) => code,
+                None => return false,
+            };
+
+            let storage_deposit = Balance::from(code.len() as u64 + TOKEN_STORAGE_OVERHEAD)
+                * env::storage_byte_cost();
+
+            let args = json!({
+                "name": name,
+                "symbol": symbol,
+                "decimals": decimals,
+                "cap": U128(cap.as_u128()),
+            });
+
+            Promise::new(token_account_id.clone())
+                .create_account()
+                .transfer(storage_deposit)
+                .deploy_contract(code)
+                .function_call(
+                    "new".to_string(),
+                    args.to_string().into_bytes(),
+                    0,
+                    GAS_FOR_TOKEN_INIT,
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_COMMAND_CALLBACK)
+                        .deploy_token_callback(
+                            utils::to_eth_hex_string(command_id),
+                            batch_index,
+                            symbol.clone(),
+                        ),
+                );
+
+            (token_account_id, TokenType::InternalBurnable)
+        } else {
+            match token_address.parse() {
+                Ok(account_id) => (account_id, TokenType::External),
+                Err(_) => return false,
+            }
+        };
+
+        self.token_addresses.insert(&symbol, &token_account_id);
+        self.token_types.insert(&symbol, &token_type);
+        self.token_mint_limits
+            .insert(&symbol, &mint_limit.as_u128());
+
+        let event = TokenDeployedEvent {
+            symbol,
+            token_address: token_account_id.to_string(),
+        };
+
+        Event::emit(&event);
+
+        true
+    }
+
+    /// `internal_mint_token` releases the tokens of a `mintToken` command to an account, tokens
+    /// deployed by the gateway are minted and external tokens are transferred from the gateway
+    ///
+    /// Arguments:
+    ///
+    /// * `payload`: The ABI encoded symbol, account and amount.
+    /// * `command_id`: The ID of the command, executed again if the mint fails.
+    /// * `batch_index`: The index of the command in its batch.
+    ///
+    /// Returns:
+    ///
+    /// A boolean value, `false` if the params are invalid, the symbol is unknown or the amount
+    /// exceeds the mint limit of the token.
+    fn internal_mint_token(
+        &mut self,
+        payload: Vec<u8>,
+        command_id: [u8; 32],
+        batch_index: u64,
+    ) -> bool {
+        let expected_output_types =
+            vec![ParamType::String, ParamType::String, ParamType::Uint(256)];
+
+        let tokens = match abi_decode(&payload, &expected_output_types) {
+            Ok(tokens) => tokens,
+            Err(_) => return false,
+        };
+
+        let symbol = tokens[0].clone().into_string().unwrap();
+        let account = tokens[1].clone().into_string().unwrap();
+        let amount = tokens[2].clone().into_uint().unwrap();
+
+        let account_id: AccountId = match account.parse() {
+            Ok(account_id) => account_id,
+            Err(_) => return false,
+        };
+
+        if amount > U256::from(Balance::MAX) {
+            return false;
+        }
+
+        let amount = amount.as_u128();
+
+        let (token_account_id, token_type) = match (
+            self.token_addresses.get(&symbol),
+            self.token_types.get(&symbol),
+        ) {
+            (Some(token_account_id), Some(token_type)) => (token_account_id, token_type),
+            _ => return false,
+        };
+
+        let mint_limit = self.token_mint_limits.get(&symbol).unwrap_or(0);
+
+        if mint_limit > 0 && amount > mint_limit {
+            return false;
+        }
+
+        let mint = match token_type {
+            TokenType::InternalBurnable => axelar_token::ext(token_account_id)
+                .with_static_gas(GAS_FOR_TOKEN_TRANSFER)
+                .mint(account_id, U128(amount)),
+            TokenType::External => ext_nep141::ext(token_account_id)
+                .with_static_gas(GAS_FOR_TOKEN_TRANSFER)
+                .with_attached_deposit(1)
+                .ft_transfer(account_id, U128(amount), None),
+        };
+
+        mint.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_COMMAND_CALLBACK)
+                .mint_token_callback(utils::to_eth_hex_string(command_id), batch_index),
+        );
+
+        true
+    }
+
     /// `internal_get_is_command_executed_key` is a function that takes a command_id as an argument and
     /// returns a vector of bytes
     ///
@@ -414,6 +1153,26 @@ impl Axelar {
         keccak256(&encoded)
     }
 
+    /// It marks a command whose external call failed as not executed, and emits a
+    /// `CommandFailedEvent` event
+    ///
+    /// Arguments:
+    ///
+    /// * `command_id`: The ID of the command.
+    /// * `batch_index`: The index of the command in its batch.
+    fn internal_rollback_command(&mut self, command_id: String, batch_index: u64) {
+        let command_id: [u8; 32] = clean_payload(command_id).try_into().unwrap();
+
+        self.internal_set_command_executed(command_id, false);
+
+        let event = CommandFailedEvent {
+            command_id: utils::to_eth_hex_string(command_id),
+            batch_index,
+        };
+
+        Event::emit(&event);
+    }
+
     /// > This function sets the value of the `bool_state` map to `true` or `false` depending on the
     /// value of the `executed` parameter
     ///
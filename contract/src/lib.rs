@@ -2,19 +2,42 @@
  * Axelar Auth contract
  *
  */
-
+// The wasm export generated for `validate_proof_legacy` calls the deprecated method.
+#[allow(deprecated)]
 mod auth_weighted;
+pub mod errors;
 mod events;
+mod external;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 mod gateway;
+mod migrate;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema;
+mod timelock;
 mod utils;
 
-use near_contract_tools::{owner::*, Owner};
+pub use utils::ct_eq;
+
+#[cfg(all(feature = "test-utils", not(debug_assertions)))]
+compile_error!("The `test-utils` feature can only be built with the `test-utils` profile");
+
+use auth_weighted::{
+    OperatorSet, DEFAULT_MAX_SIGNATURES, DEFAULT_MIN_OPERATORS, OLD_KEY_RETENTION,
+};
+use errors::AuthError;
+use events::OwnershipTransferredEvent;
+use gateway::{TokenType, GAS_FOR_EXECUTE_CALLBACK, GAS_FOR_VALIDATE_PROOF};
+use migrate::OldAxelar;
+use near_contract_tools::standard::nep297::Event;
+use near_contract_tools::{migrate::*, owner::*, pause::*, Migrate, Pause};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LookupMap;
-use near_sdk::env::predecessor_account_id;
-use near_sdk::near_bindgen;
-use utils::clean_payload;
+use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::env::{self, predecessor_account_id};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, near_bindgen, AccountId, Balance, FunctionError, Gas};
 use utils::keccak256;
+use utils::try_clean_payload;
 
 /// `Axelar` is a struct that contains a `current_epoch` field, a `hash_for_epoch` field, an
 /// `epoch_for_hash` field, a `prefix_command_executed` field, a `prefix_contract_call_approved` field,
@@ -30,22 +53,65 @@ use utils::keccak256;
 /// * `hash_for_epoch`: This is a map that stores the hash of the block that was used to create the
 /// epoch.
 /// * `epoch_for_hash`: This is a mapping from a hash to an epoch.
+/// * `old_key_retention`: The number of epochs for which the previous operators are still valid.
+/// * `timestamp_for_epoch`: This is a map from an epoch to the block timestamp it was created at.
+/// * `operator_set_for_epoch`: This is a map from an epoch within the key retention window to its
+///   decoded operators, weights and threshold.
+/// * `max_proof_age`: The number of nanoseconds after its creation for which an epoch can validate
+///   proofs, unbounded if `None`.
+/// * `max_signatures`: The maximum number of signatures a proof can carry.
+/// * `min_operators`: The minimum number of operators an operatorship can be transferred to.
+/// * `timelock_duration`: The number of nanoseconds a sensitive owner operation has to be scheduled
+///   for before it is executed, not time-locked if `None`.
+/// * `operation_ready_at`: This is a map from the hash of a scheduled operation to the block
+///   timestamp from which it can be executed.
+/// * `last_pruned_epoch`: The last epoch `prune_expired` went through, the next pruning starts
+///   after it.
+/// * `auth_module`: The account of the contract that validates the proofs of the command batches.
+/// * `validate_proof_gas`: The gas attached to the proof validation by the auth module.
+/// * `callback_gas`: The gas reserved for the callback executing the commands of a batch.
+/// * `chain_id`: The chain id the command batches must be signed for.
 /// * `prefix_command_executed`: This is the prefix for the key that stores the boolean value of whether
 /// a command has been executed.
 /// * `prefix_contract_call_approved`: This is the prefix for the key that stores the boolean value of
 /// whether a contract call has been approved.
 /// * `bool_state`: This is a map that stores the state of the contract.
+/// * `token_code`: The code of the NEP-141 contract deployed by the `deployToken` command.
+/// * `token_addresses`: This is a map from a token symbol to the account of its contract.
+/// * `token_types`: This is a map from a token symbol to whether the gateway deployed the token.
+/// * `token_mint_limits`: This is a map from a token symbol to the maximum amount a single
+///   `mintToken` command can mint, unbounded if 0.
+///
+/// The fields are mirrored by `schema::AxelarLayout`, which must be updated with them.
 #[near_bindgen]
-#[derive(Owner, BorshDeserialize, BorshSerialize)]
+#[derive(Pause, Migrate, BorshDeserialize, BorshSerialize)]
+#[migrate(from = "OldAxelar")]
 pub struct Axelar {
     // Auth Weighted
     current_epoch: u64,
     hash_for_epoch: LookupMap<u64, [u8; 32]>,
     epoch_for_hash: LookupMap<[u8; 32], u64>,
+    old_key_retention: u64,
+    timestamp_for_epoch: LookupMap<u64, u64>,
+    operator_set_for_epoch: LookupMap<u64, OperatorSet>,
+    max_proof_age: Option<u64>,
+    max_signatures: u64,
+    min_operators: u32,
+    timelock_duration: Option<u64>,
+    operation_ready_at: LookupMap<[u8; 32], u64>,
+    last_pruned_epoch: u64,
     // Gateway
+    auth_module: AccountId,
+    validate_proof_gas: Gas,
+    callback_gas: Gas,
+    chain_id: u64,
     prefix_command_executed: [u8; 32],
     prefix_contract_call_approved: [u8; 32],
     bool_state: LookupMap<[u8; 32], bool>,
+    token_code: LazyOption<Vec<u8>>,
+    token_addresses: LookupMap<String, AccountId>,
+    token_types: LookupMap<String, TokenType>,
+    token_mint_limits: LookupMap<String, Balance>,
 }
 
 /// This is a default implementation of the `Axelar` struct.
@@ -56,44 +122,266 @@ impl Default for Axelar {
             current_epoch: 0,
             hash_for_epoch: LookupMap::new(b"hash_for_epoch".to_vec()),
             epoch_for_hash: LookupMap::new(b"epoch_for_hash".to_vec()),
+            old_key_retention: OLD_KEY_RETENTION.into(),
+            timestamp_for_epoch: LookupMap::new(b"timestamp_for_epoch".to_vec()),
+            operator_set_for_epoch: LookupMap::new(b"operator_set_for_epoch".to_vec()),
+            max_proof_age: None,
+            max_signatures: DEFAULT_MAX_SIGNATURES,
+            min_operators: DEFAULT_MIN_OPERATORS,
+            timelock_duration: None,
+            operation_ready_at: LookupMap::new(b"operation_ready_at".to_vec()),
+            last_pruned_epoch: 0,
             // Gateway
+            auth_module: env::current_account_id(),
+            validate_proof_gas: GAS_FOR_VALIDATE_PROOF,
+            callback_gas: GAS_FOR_EXECUTE_CALLBACK,
+            chain_id: 0,
             prefix_command_executed: keccak256(b"command-executed"),
             prefix_contract_call_approved: keccak256(b"contract-call-approved"),
             bool_state: LookupMap::new(b"bool_state".to_vec()),
+            token_code: LazyOption::new(b"token_code".to_vec(), None),
+            token_addresses: LookupMap::new(b"token_addresses".to_vec()),
+            token_types: LookupMap::new(b"token_types".to_vec()),
+            token_mint_limits: LookupMap::new(b"token_mint_limits".to_vec()),
+        }
+    }
+}
+
+impl Owner for Axelar {
+    /// `update_owner` updates the owner like the default implementation, and also emits an
+    /// `OwnershipTransferredEvent` so the owner of the contract can be tracked with its own events
+    ///
+    /// Arguments:
+    ///
+    /// * `new`: The account of the new owner, `None` to renounce the ownership.
+    fn update_owner(&mut self, new: Option<AccountId>) {
+        let old = Self::slot_owner().read();
+
+        if old != new {
+            OwnerEvent::Transfer {
+                old: old.clone(),
+                new: new.clone(),
+            }
+            .emit();
+
+            OwnershipTransferredEvent {
+                previous_owner: old.map(String::from),
+                new_owner: new.clone().map(String::from),
+            }
+            .emit();
+
+            self.update_owner_unchecked(new);
+        }
+    }
+}
+
+/// The ownership methods of the `Owner` derive, implemented here as `update_owner` is overridden
+#[near_bindgen]
+impl OwnerExternal for Axelar {
+    fn own_get_owner(&self) -> Option<AccountId> {
+        Self::slot_owner().read()
+    }
+
+    fn own_get_proposed_owner(&self) -> Option<AccountId> {
+        Self::slot_proposed_owner().read()
+    }
+
+    #[payable]
+    fn own_renounce_owner(&mut self) {
+        assert_one_yocto();
+        self.renounce_owner()
+    }
+
+    #[payable]
+    fn own_propose_owner(&mut self, account_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.propose_owner(account_id);
+    }
+
+    #[payable]
+    fn own_accept_owner(&mut self) {
+        assert_one_yocto();
+        self.accept_owner();
+    }
+}
+
+impl MigrateHook for Axelar {
+    /// `on_migrate` converts the `1.0.0` state into the current layout, keeping the epoch, the
+    /// operators hashes and the gateway state
+    ///
+    /// Arguments:
+    ///
+    /// * `old_schema`: The state read from storage in the `1.0.0` layout.
+    ///
+    /// Returns:
+    ///
+    /// The migrated contract.
+    fn on_migrate(
+        old_schema: <Self as MigrateController>::OldSchema,
+    ) -> <Self as MigrateController>::NewSchema {
+        Self::require_owner();
+
+        Self {
+            // Auth Weighted
+            current_epoch: old_schema.current_epoch,
+            hash_for_epoch: old_schema.hash_for_epoch,
+            epoch_for_hash: old_schema.epoch_for_hash,
+            old_key_retention: OLD_KEY_RETENTION.into(),
+            timestamp_for_epoch: LookupMap::new(b"timestamp_for_epoch".to_vec()),
+            operator_set_for_epoch: LookupMap::new(b"operator_set_for_epoch".to_vec()),
+            max_proof_age: None,
+            max_signatures: DEFAULT_MAX_SIGNATURES,
+            min_operators: DEFAULT_MIN_OPERATORS,
+            timelock_duration: None,
+            operation_ready_at: LookupMap::new(b"operation_ready_at".to_vec()),
+            last_pruned_epoch: 0,
+            // Gateway
+            auth_module: env::current_account_id(),
+            validate_proof_gas: GAS_FOR_VALIDATE_PROOF,
+            callback_gas: GAS_FOR_EXECUTE_CALLBACK,
+            chain_id: 0,
+            prefix_command_executed: old_schema.prefix_command_executed,
+            prefix_contract_call_approved: old_schema.prefix_contract_call_approved,
+            bool_state: old_schema.bool_state,
+            token_code: LazyOption::new(b"token_code".to_vec(), None),
+            token_addresses: LookupMap::new(b"token_addresses".to_vec()),
+            token_types: LookupMap::new(b"token_types".to_vec()),
+            token_mint_limits: LookupMap::new(b"token_mint_limits".to_vec()),
         }
     }
 }
 
+/// `AxelarConfig` is the optional settings of a new contract, each of them is defaulted when it is
+/// omitted.
+///
+/// Properties:
+///
+/// * `old_key_retention`: The number of epochs for which the previous operators are still valid,
+///   the current epoch included, so it can not be 0. Defaults to `OLD_KEY_RETENTION`.
+/// * `start_epoch`: The epoch to continue from when migrating from a previous deployment, the
+///   recent operators are assigned the epochs following it. Defaults to 0.
+/// * `max_proof_age_ns`: The number of nanoseconds after its creation for which an epoch can
+///   validate proofs. Defaults to no limit.
+/// * `min_operators`: The minimum number of operators an operatorship can be transferred to,
+///   the recent operators included. Defaults to `DEFAULT_MIN_OPERATORS`.
+/// * `timelock_duration_ns`: The number of nanoseconds the sensitive owner operations have to be
+///   scheduled for before they are executed. Defaults to no time-lock. A separate auth module
+///   owned by a gateway must not be time-locked, as the `transferOperatorship` commands transfer
+///   its operatorship directly.
+/// * `chain_id`: The chain id the command batches must be signed for, so that batches signed for
+///   another deployment can not be replayed. Defaults to 0.
+/// * `auth_module`: The account of the contract that validates the proofs of the command
+///   batches. Defaults to this contract.
+/// * `validate_proof_gas`: The gas attached to the proof validation by the auth module, which
+///   grows with the number of signers. Defaults to `GAS_FOR_VALIDATE_PROOF`.
+/// * `callback_gas`: The gas reserved for the callback executing the commands of a batch.
+///   Defaults to `GAS_FOR_EXECUTE_CALLBACK`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct AxelarConfig {
+    pub old_key_retention: Option<u64>,
+    pub start_epoch: Option<u64>,
+    pub max_proof_age_ns: Option<u64>,
+    pub min_operators: Option<u32>,
+    pub timelock_duration_ns: Option<u64>,
+    pub chain_id: Option<u64>,
+    pub auth_module: Option<AccountId>,
+    pub validate_proof_gas: Option<Gas>,
+    pub callback_gas: Option<Gas>,
+}
+
 #[near_bindgen]
 impl Axelar {
     /// `new` is called when the contract is first deployed, and it initializes the contract's state
     ///
     /// Arguments:
     ///
-    /// * `recent_operators`: A list of account IDs that will be given operator status.
+    /// * `recent_operators`: The ABI encoded operators, weights and threshold of each epoch, each of
+    /// them validated like an operatorship transfer. The contract can be initialized without
+    /// operators, such as a gateway with a separate auth module, but it can not validate proofs
+    /// until the operatorship is transferred, which is logged as a warning.
+    /// * `config`: The optional settings of the contract, all of them are defaulted if it is
+    /// omitted.
     ///
     /// Returns:
     ///
     /// The contract is being returned.
     #[init]
-    pub fn new(recent_operators: Vec<String>) -> Self {
+    pub fn new(recent_operators: Vec<String>, config: Option<AxelarConfig>) -> Self {
+        let AxelarConfig {
+            old_key_retention,
+            start_epoch,
+            max_proof_age_ns,
+            min_operators,
+            timelock_duration_ns,
+            chain_id,
+            auth_module,
+            validate_proof_gas,
+            callback_gas,
+        } = config.unwrap_or_default();
+
+        if old_key_retention == Some(0) {
+            AuthError::InvalidKeyRetention.panic();
+        }
+
         let mut contract = Self {
             // Auth Weighted
-            current_epoch: 0,
+            current_epoch: start_epoch.unwrap_or(0),
             hash_for_epoch: LookupMap::new(b"hash_for_epoch".to_vec()),
             epoch_for_hash: LookupMap::new(b"epoch_for_hash".to_vec()),
+            old_key_retention: old_key_retention.unwrap_or(OLD_KEY_RETENTION.into()),
+            timestamp_for_epoch: LookupMap::new(b"timestamp_for_epoch".to_vec()),
+            operator_set_for_epoch: LookupMap::new(b"operator_set_for_epoch".to_vec()),
+            max_proof_age: max_proof_age_ns,
+            max_signatures: DEFAULT_MAX_SIGNATURES,
+            min_operators: min_operators.unwrap_or(DEFAULT_MIN_OPERATORS),
+            timelock_duration: timelock_duration_ns,
+            operation_ready_at: LookupMap::new(b"operation_ready_at".to_vec()),
+            last_pruned_epoch: 0,
             // Gateway
+            auth_module: auth_module.unwrap_or_else(env::current_account_id),
+            validate_proof_gas: validate_proof_gas.unwrap_or(GAS_FOR_VALIDATE_PROOF),
+            callback_gas: callback_gas.unwrap_or(GAS_FOR_EXECUTE_CALLBACK),
+            chain_id: chain_id.unwrap_or(0),
             prefix_command_executed: keccak256(b"command-executed"),
             prefix_contract_call_approved: keccak256(b"contract-call-approved"),
             bool_state: LookupMap::new(b"bool_state".to_vec()),
+            token_code: LazyOption::new(b"token_code".to_vec(), None),
+            token_addresses: LookupMap::new(b"token_addresses".to_vec()),
+            token_types: LookupMap::new(b"token_types".to_vec()),
+            token_mint_limits: LookupMap::new(b"token_mint_limits".to_vec()),
         };
 
         Owner::init(&mut contract, &predecessor_account_id());
 
+        if recent_operators.is_empty() {
+            env::log_str("Warning: no operators were seeded, proofs can not be validated");
+        }
+
         for operator in recent_operators {
-            contract.internal_transfer_operatorship(clean_payload(operator));
+            let params =
+                try_clean_payload(operator).unwrap_or_else(|| AuthError::MalformedParams.panic());
+
+            contract
+                .internal_transfer_operatorship(params)
+                .unwrap_or_else(|error| error.panic());
         }
 
         contract
     }
+
+    /// `pause` blocks the operatorship transfers until the contract is unpaused, proofs can still be
+    /// validated while paused
+    #[payable]
+    pub fn pause(&mut self) {
+        Self::require_owner();
+        Pause::pause(self);
+    }
+
+    /// `unpause` allows the operatorship transfers again
+    #[payable]
+    pub fn unpause(&mut self) {
+        Self::require_owner();
+        Pause::unpause(self);
+    }
 }
@@ -0,0 +1,31 @@
+/*
+ * Axelar state migration
+ *
+ */
+use near_sdk::borsh::{self, BorshDeserialize};
+use near_sdk::collections::LookupMap;
+
+/// `OldAxelar` is the `1.0.0` state layout of the `Axelar` contract, before the key retention was
+/// stored in state.
+///
+/// Properties:
+///
+/// * `current_epoch`: The current epoch number.
+/// * `hash_for_epoch`: This is a map from an epoch to the hash of its operators.
+/// * `epoch_for_hash`: This is a mapping from a hash to an epoch.
+/// * `prefix_command_executed`: This is the prefix for the key that stores the boolean value of whether
+///   a command has been executed.
+/// * `prefix_contract_call_approved`: This is the prefix for the key that stores the boolean value of
+///   whether a contract call has been approved.
+/// * `bool_state`: This is a map that stores the state of the contract.
+#[derive(BorshDeserialize)]
+pub struct OldAxelar {
+    // Auth Weighted
+    pub current_epoch: u64,
+    pub hash_for_epoch: LookupMap<u64, [u8; 32]>,
+    pub epoch_for_hash: LookupMap<[u8; 32], u64>,
+    // Gateway
+    pub prefix_command_executed: [u8; 32],
+    pub prefix_contract_call_approved: [u8; 32],
+    pub bool_state: LookupMap<[u8; 32], bool>,
+}
@@ -0,0 +1,177 @@
+/*
+ * Axelar state schema
+ *
+ */
+use crate::auth_weighted::OperatorSet;
+use crate::gateway::TokenType;
+use crate::Axelar;
+use near_sdk::borsh::schema::{BorshSchemaContainer, Declaration, Definition, Fields};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSchema, BorshSerialize};
+use near_sdk::serde_json::{json, Map, Value};
+use near_sdk::{AccountId, Balance, Gas};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// `StorageMap` is the Borsh layout of a `LookupMap`, only its storage prefix is in the state, the
+/// keys and values are stored under it.
+///
+/// Properties:
+///
+/// * `key_prefix`: The storage prefix of the entries.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct StorageMap<K, V> {
+    key_prefix: Vec<u8>,
+    #[borsh_skip]
+    entries: PhantomData<(K, V)>,
+}
+
+impl<K: BorshSchema, V: BorshSchema> BorshSchema for StorageMap<K, V> {
+    fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+        Self::add_definition(
+            Self::declaration(),
+            Definition::Struct {
+                fields: Fields::NamedFields(vec![(
+                    "key_prefix".to_string(),
+                    <Vec<u8>>::declaration(),
+                )]),
+            },
+            definitions,
+        );
+        <Vec<u8>>::add_definitions_recursively(definitions);
+        K::add_definitions_recursively(definitions);
+        V::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        format!("LookupMap<{}, {}>", K::declaration(), V::declaration())
+    }
+}
+
+/// `StorageValue` is the Borsh layout of a `LazyOption`, only its storage key is in the state, the
+/// value is stored under it.
+///
+/// Properties:
+///
+/// * `storage_key`: The storage key of the value.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct StorageValue<T> {
+    storage_key: Vec<u8>,
+    #[borsh_skip]
+    value: PhantomData<T>,
+}
+
+impl<T: BorshSchema> BorshSchema for StorageValue<T> {
+    fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+        Self::add_definition(
+            Self::declaration(),
+            Definition::Struct {
+                fields: Fields::NamedFields(vec![(
+                    "storage_key".to_string(),
+                    <Vec<u8>>::declaration(),
+                )]),
+            },
+            definitions,
+        );
+        <Vec<u8>>::add_definitions_recursively(definitions);
+        T::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        format!("LazyOption<{}>", T::declaration())
+    }
+}
+
+/// `AxelarLayout` mirrors the fields of `Axelar` as they are stored under the `STATE` key, with the
+/// collections replaced by their Borsh layout, as the collections have no Borsh schema. It must be
+/// updated with `Axelar`, which `layout_matches_state` checks.
+#[derive(BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct AxelarLayout {
+    // Auth Weighted
+    current_epoch: u64,
+    hash_for_epoch: StorageMap<u64, [u8; 32]>,
+    epoch_for_hash: StorageMap<[u8; 32], u64>,
+    old_key_retention: u64,
+    timestamp_for_epoch: StorageMap<u64, u64>,
+    operator_set_for_epoch: StorageMap<u64, OperatorSet>,
+    max_proof_age: Option<u64>,
+    max_signatures: u64,
+    min_operators: u32,
+    timelock_duration: Option<u64>,
+    operation_ready_at: StorageMap<[u8; 32], u64>,
+    last_pruned_epoch: u64,
+    // Gateway
+    auth_module: AccountId,
+    validate_proof_gas: Gas,
+    callback_gas: Gas,
+    chain_id: u64,
+    prefix_command_executed: [u8; 32],
+    prefix_contract_call_approved: [u8; 32],
+    bool_state: StorageMap<[u8; 32], bool>,
+    token_code: StorageValue<Vec<u8>>,
+    token_addresses: StorageMap<String, AccountId>,
+    token_types: StorageMap<String, TokenType>,
+    token_mint_limits: StorageMap<String, Balance>,
+}
+
+/// `layout_matches_state` checks that the state of a contract reads back as `AxelarLayout` and is
+/// written again to the same bytes, so the schema can not silently drift from `Axelar`
+///
+/// Returns:
+///
+/// Whether the default state of `Axelar` round-trips through `AxelarLayout`.
+pub fn layout_matches_state() -> bool {
+    let state = Axelar::default().try_to_vec().unwrap();
+
+    AxelarLayout::try_from_slice(&state)
+        .and_then(|layout| layout.try_to_vec())
+        .is_ok_and(|layout| layout == state)
+}
+
+/// `state_schema` returns the Borsh schema of the contract state as JSON, with the definitions
+/// sorted by declaration so the output is stable
+///
+/// Returns:
+///
+/// The declaration of the state and the definition of every type it contains.
+pub fn state_schema() -> Value {
+    let BorshSchemaContainer {
+        declaration,
+        definitions,
+    } = AxelarLayout::schema_container();
+
+    let mut definitions = definitions.into_iter().collect::<Vec<_>>();
+    definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    json!({
+        "declaration": declaration,
+        "definitions": definitions
+            .into_iter()
+            .map(|(declaration, definition)| (declaration, definition_to_json(definition)))
+            .collect::<Map<_, _>>(),
+    })
+}
+
+/// It converts a Borsh definition to JSON, as `Definition` does not implement `Serialize`
+///
+/// Arguments:
+///
+/// * `definition`: The definition of a type.
+///
+/// Returns:
+///
+/// The kind of the definition, with its elements, variants or fields.
+fn definition_to_json(definition: Definition) -> Value {
+    match definition {
+        Definition::Array { length, elements } => {
+            json!({ "array": { "length": length, "elements": elements } })
+        }
+        Definition::Sequence { elements } => json!({ "sequence": { "elements": elements } }),
+        Definition::Tuple { elements } => json!({ "tuple": { "elements": elements } }),
+        Definition::Enum { variants } => json!({ "enum": { "variants": variants } }),
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => json!({ "struct": { "fields": fields } }),
+            Fields::UnnamedFields(fields) => json!({ "struct": { "unnamed_fields": fields } }),
+            Fields::Empty => json!({ "struct": {} }),
+        },
+    }
+}
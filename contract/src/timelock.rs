@@ -0,0 +1,234 @@
+/*
+ * Axelar owner time-lock
+ *
+ */
+use crate::auth_weighted::TransferResult;
+use crate::errors::TimelockError;
+use crate::events::{OperationCancelledEvent, OperationExecutedEvent, OperationScheduledEvent};
+use crate::utils::{self, clean_payload, keccak256};
+use crate::{Axelar, AxelarExt};
+use ethabi::ethereum_types::H256;
+use near_contract_tools::owner::*;
+use near_contract_tools::standard::nep297::Event;
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, FunctionError};
+
+/// `TimelockedOperation` is an owner operation that has to be scheduled before it is executed
+/// while a time-lock is set.
+///
+/// Variants:
+///
+/// * `TransferOperatorship`: Transfers the operatorship to the ABI encoded operators, weights and
+///   threshold of `params`, like `transfer_operatorship`.
+/// * `RevokeEpoch`: Revokes the operators of `epoch`, like `revoke_epoch`.
+/// * `ForceSetEpoch`: Forces the current epoch to `epoch` with the operators `hash`, like
+///   `force_set_epoch`.
+/// * `SetTimelockDuration`: Updates the time-lock duration, `None` removes the time-lock.
+/// * `SetAuthModule`: Changes the contract that validates the proofs of the command batches, like
+///   `set_auth_module`.
+/// * `SetMaxProofAge`: Updates the maximum proof age, like `set_max_proof_age`.
+/// * `SetMaxSignatures`: Updates the maximum number of signatures of a proof, like
+///   `set_max_signatures`.
+/// * `TransferOperatorshipEd25519`: Transfers the operatorship to the Ed25519 operators, weights
+///   and threshold of `params`, like `transfer_operatorship_ed25519`.
+#[derive(BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum TimelockedOperation {
+    TransferOperatorship { params: String },
+    RevokeEpoch { epoch: u64 },
+    ForceSetEpoch { epoch: u64, hash: String },
+    SetTimelockDuration { timelock_duration_ns: Option<u64> },
+    SetAuthModule { auth_module: AccountId },
+    SetMaxProofAge { max_proof_age_ns: Option<u64> },
+    SetMaxSignatures { max_signatures: u64 },
+    TransferOperatorshipEd25519 { params: String },
+}
+
+impl TimelockedOperation {
+    /// `hash` returns the hash an operation is scheduled under
+    ///
+    /// Returns:
+    ///
+    /// The keccak256 hash of the Borsh encoded operation.
+    pub fn hash(&self) -> [u8; 32] {
+        keccak256(self.try_to_vec().unwrap())
+    }
+}
+
+#[near_bindgen]
+impl Axelar {
+    /// `timelock_duration` returns the number of nanoseconds a sensitive owner operation has to be
+    /// scheduled for before it can be executed
+    ///
+    /// Returns:
+    ///
+    /// The time-lock duration in nanoseconds, `None` if the owner operations are not time-locked.
+    pub fn timelock_duration(&self) -> Option<u64> {
+        self.timelock_duration
+    }
+
+    /// `operation_ready_at` returns when a scheduled operation can be executed
+    ///
+    /// Arguments:
+    ///
+    /// * `operation`: The scheduled operation.
+    ///
+    /// Returns:
+    ///
+    /// The block timestamp in nanoseconds from which the operation can be executed, `None` if it is
+    /// not scheduled.
+    pub fn operation_ready_at(&self, operation: TimelockedOperation) -> Option<u64> {
+        self.operation_ready_at.get(&operation.hash())
+    }
+
+    /// `schedule_operation` schedules an operation to be executed once the time-lock duration has
+    /// elapsed. The delay is fixed when the operation is scheduled, a later change of the duration
+    /// does not apply to it.
+    ///
+    /// Arguments:
+    ///
+    /// * `operation`: The operation to schedule.
+    ///
+    /// Returns:
+    ///
+    /// The block timestamp in nanoseconds from which the operation can be executed.
+    #[payable]
+    pub fn schedule_operation(&mut self, operation: TimelockedOperation) -> u64 {
+        Self::require_owner();
+
+        let operation_hash = operation.hash();
+
+        if self.operation_ready_at.contains_key(&operation_hash) {
+            TimelockError::AlreadyScheduled.panic();
+        }
+
+        let ready_at = env::block_timestamp().saturating_add(self.timelock_duration.unwrap_or(0));
+
+        self.operation_ready_at.insert(&operation_hash, &ready_at);
+
+        OperationScheduledEvent {
+            operation_hash: utils::to_eth_hex_string(operation_hash),
+            ready_at,
+        }
+        .emit();
+
+        ready_at
+    }
+
+    /// `execute_operation` executes a scheduled operation once it is ready, an operation has to be
+    /// scheduled again to be executed again
+    ///
+    /// Arguments:
+    ///
+    /// * `operation`: The scheduled operation.
+    ///
+    /// Returns:
+    ///
+    /// The `TransferResult` of a `TransferOperatorship` or `TransferOperatorshipEd25519` operation,
+    /// as returned by the operatorship transfers, `None` for the other operations.
+    #[payable]
+    pub fn execute_operation(&mut self, operation: TimelockedOperation) -> Option<TransferResult> {
+        Self::require_owner();
+
+        let operation_hash = operation.hash();
+
+        let ready_at = self
+            .operation_ready_at
+            .remove(&operation_hash)
+            .unwrap_or_else(|| TimelockError::NotScheduled.panic());
+
+        if env::block_timestamp() < ready_at {
+            TimelockError::NotReady.panic();
+        }
+
+        let transfer_result = match operation {
+            TimelockedOperation::TransferOperatorship { params } => Some(
+                self.internal_transfer_operatorship(clean_payload(params))
+                    .unwrap_or_else(|error| error.panic()),
+            ),
+            TimelockedOperation::RevokeEpoch { epoch } => {
+                self.internal_revoke_epoch(epoch);
+                None
+            }
+            TimelockedOperation::ForceSetEpoch { epoch, hash } => {
+                self.internal_force_set_epoch(epoch, hash);
+                None
+            }
+            TimelockedOperation::SetTimelockDuration {
+                timelock_duration_ns,
+            } => {
+                self.timelock_duration = timelock_duration_ns;
+                None
+            }
+            TimelockedOperation::SetAuthModule { auth_module } => {
+                self.auth_module = auth_module;
+                None
+            }
+            TimelockedOperation::SetMaxProofAge { max_proof_age_ns } => {
+                self.max_proof_age = max_proof_age_ns;
+                None
+            }
+            TimelockedOperation::SetMaxSignatures { max_signatures } => {
+                self.max_signatures = max_signatures;
+                None
+            }
+            TimelockedOperation::TransferOperatorshipEd25519 { params } => Some(
+                self.internal_transfer_operatorship_of::<H256>(clean_payload(params))
+                    .unwrap_or_else(|error| error.panic()),
+            ),
+        };
+
+        OperationExecutedEvent {
+            operation_hash: utils::to_eth_hex_string(operation_hash),
+        }
+        .emit();
+
+        transfer_result
+    }
+
+    /// `cancel_operation` removes a scheduled operation before it is executed
+    ///
+    /// Arguments:
+    ///
+    /// * `operation`: The scheduled operation.
+    #[payable]
+    pub fn cancel_operation(&mut self, operation: TimelockedOperation) {
+        Self::require_owner();
+
+        let operation_hash = operation.hash();
+
+        if self.operation_ready_at.remove(&operation_hash).is_none() {
+            TimelockError::NotScheduled.panic();
+        }
+
+        OperationCancelledEvent {
+            operation_hash: utils::to_eth_hex_string(operation_hash),
+        }
+        .emit();
+    }
+
+    /// `set_timelock_duration` sets the time-lock of the owner operations. Once a time-lock is set,
+    /// it can only be changed by scheduling a `SetTimelockDuration` operation.
+    ///
+    /// Arguments:
+    ///
+    /// * `timelock_duration_ns`: The time-lock duration in nanoseconds, `None` removes the
+    ///   time-lock.
+    #[payable]
+    pub fn set_timelock_duration(&mut self, timelock_duration_ns: Option<u64>) {
+        Self::require_owner();
+        self.require_no_timelock();
+        self.timelock_duration = timelock_duration_ns;
+    }
+}
+
+impl Axelar {
+    /// It panics if the owner operations are time-locked, so they can only be executed through
+    /// `execute_operation`
+    pub(crate) fn require_no_timelock(&self) {
+        if self.timelock_duration.is_some() {
+            TimelockError::TimeLocked.panic();
+        }
+    }
+}
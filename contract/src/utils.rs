@@ -4,15 +4,29 @@
  */
 use ethabi::decode;
 use ethabi::encode;
-use ethabi::ethereum_types::H256;
+use ethabi::ethereum_types::{H160, H256, U256};
 use ethabi::Address;
 use ethabi::ParamType;
 use ethabi::Token;
+use near_sdk::borsh::schema::{Declaration, Definition, Fields};
+use near_sdk::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use near_sdk::env;
+use near_sdk::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::fmt;
+use subtle::ConstantTimeEq;
 use uint::hex;
 
 /// It takes a hash and a signature, and returns the address that signed the hash
 ///
+/// The recovery is done by the `ecrecover` host function, which is much cheaper in gas than
+/// verifying the curve operations in the contract. Signatures with an `s` value in the upper half
+/// of the curve order are rejected, matching the EVM `ecrecover` convention, so that a signature
+/// has a single valid representation. The recovery byte `v` can be either 0/1 or 27/28, any other
+/// value is rejected. Signatures are either 65 bytes `r || s || v`, or 64 bytes `r || yParityAndS`
+/// in the EIP-2098 compact form, any other length is rejected.
+///
 /// Arguments:
 ///
 /// * `hash`: The hash of the message to be signed.
@@ -20,27 +34,194 @@ use uint::hex;
 ///
 /// Returns:
 ///
-/// The address of the signer.
+/// The Ethereum address of the signer, the last 20 bytes of the keccak256 hash of the 64-byte
+/// public key returned by the host, so it compares directly to the operators.
 pub fn ecrecover(hash: H256, signature: &[u8]) -> Result<Address, ()> {
-    assert_eq!(signature.len(), 65);
-
-    let hash = secp256k1::Message::parse_slice(hash.as_bytes()).unwrap();
-    let v = signature[64];
-    let signature = secp256k1::Signature::parse_slice(&signature[0..64]).unwrap();
-    let bit = match v {
-        0..=26 => v,
-        _ => v - 27,
-    };
+    let mut rs = [0u8; 64];
+
+    let v = match signature.len() {
+        65 => {
+            rs.copy_from_slice(&signature[0..64]);
+
+            // `v` is either the raw recovery id or offset by 27 as in Ethereum signatures
+            match signature[64] {
+                v @ (0 | 1) => v,
+                v @ (27 | 28) => v - 27,
+                _ => return Err(()),
+            }
+        }
+        64 => {
+            rs.copy_from_slice(signature);
 
-    if let Ok(recovery_id) = secp256k1::RecoveryId::parse(bit) {
-        if let Ok(public_key) = secp256k1::recover(&hash, &signature, &recovery_id) {
-            // recover returns a 65-byte key, but addresses come from the raw 64-byte key
-            let r = sha3::Keccak256::digest(&public_key.serialize()[1..]);
-            return Ok(Address::from_slice(&r[12..]));
+            // EIP-2098 compact signatures carry the recovery id in the top bit of `s`
+            let v = rs[32] >> 7;
+            rs[32] &= 0x7f;
+            v
         }
+        _ => return Err(()),
+    };
+
+    #[cfg(feature = "test-utils")]
+    RECOVERY_COUNT.with(|count| count.set(count.get() + 1));
+
+    let public_key = env::ecrecover(hash.as_bytes(), &rs, v, true).ok_or(())?;
+
+    Ok(public_key_to_address(&public_key))
+}
+
+/// It computes the Ethereum address of a secp256k1 public key, so operators can be registered from
+/// their public keys
+///
+/// Arguments:
+///
+/// * `public_key`: The 64-byte uncompressed public key, without the `0x04` prefix.
+///
+/// Returns:
+///
+/// The last 20 bytes of the keccak256 hash of the public key.
+pub fn public_key_to_address(public_key: &[u8]) -> H160 {
+    H160::from_slice(&keccak256(public_key)[12..])
+}
+
+/// It takes a hash and an Ed25519 signature prefixed with the public key of its signer, and returns
+/// the public key if the signature is valid
+///
+/// Ed25519 signatures can not be recovered, so the signer is given with the signature and the
+/// signature is verified against it, in the contract as the host has no Ed25519 verification. The
+/// verification is strict, so a signature has a single valid representation and the public keys
+/// of small order are rejected.
+///
+/// Arguments:
+///
+/// * `hash`: The hash of the message to be signed.
+/// * `signature`: The 96 bytes `publicKey || signature`.
+///
+/// Returns:
+///
+/// The public key of the signer.
+pub fn ed25519_verify(hash: H256, signature: &[u8]) -> Result<H256, ()> {
+    if signature.len() != 96 {
+        return Err(());
     }
 
-    Err(())
+    let (public_key, signature) = signature.split_at(32);
+    let signature = ed25519_dalek::Signature::from_bytes(signature).map_err(|_| ())?;
+
+    ed25519_dalek::PublicKey::from_bytes(public_key)
+        .and_then(|key| key.verify_strict(hash.as_bytes(), &signature))
+        .map_err(|_| ())?;
+
+    Ok(H256::from_slice(public_key))
+}
+
+/// `KeyType` is the type of the keys of an operator set, all the operators of an epoch sign with
+/// the same type of key.
+///
+/// Variants:
+///
+/// * `Secp256k1`: The operators are Ethereum addresses, recovered from the signatures.
+/// * `Ed25519`: The operators are 32-byte Ed25519 public keys, verified against the signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    Secp256k1 = 0,
+    Ed25519 = 1,
+}
+
+/// `OperatorKey` is the key the operators of a `KeyType` are registered and matched with
+pub trait OperatorKey: Copy + Ord + AsRef<[u8]> + fmt::Display + fmt::LowerHex {
+    /// The type of the key, tagged in the operators hash
+    const KEY_TYPE: KeyType;
+
+    /// The ABI type of the key in the operators params
+    const PARAM_TYPE: ParamType;
+
+    /// It decodes the key from its ABI token, `None` if the token is not of `PARAM_TYPE`
+    fn from_token(token: Token) -> Option<Self>;
+
+    /// It encodes the key as its ABI token
+    fn into_token(self) -> Token;
+
+    /// It checks whether the key can be registered as an operator
+    fn is_valid_operator(&self) -> bool;
+
+    /// It returns the key that signed the hash, or an error if the signature is invalid
+    fn signer(hash: H256, signature: &[u8]) -> Result<Self, ()>;
+}
+
+impl OperatorKey for H160 {
+    const KEY_TYPE: KeyType = KeyType::Secp256k1;
+    const PARAM_TYPE: ParamType = ParamType::Address;
+
+    fn from_token(token: Token) -> Option<Self> {
+        token.into_address()
+    }
+
+    fn into_token(self) -> Token {
+        Token::Address(self)
+    }
+
+    fn is_valid_operator(&self) -> bool {
+        is_valid_operator_address(self)
+    }
+
+    fn signer(hash: H256, signature: &[u8]) -> Result<Self, ()> {
+        ecrecover(hash, signature)
+    }
+}
+
+impl OperatorKey for H256 {
+    const KEY_TYPE: KeyType = KeyType::Ed25519;
+    const PARAM_TYPE: ParamType = ParamType::FixedBytes(32);
+
+    fn from_token(token: Token) -> Option<Self> {
+        token
+            .into_fixed_bytes()
+            .filter(|bytes| bytes.len() == 32)
+            .map(|bytes| H256::from_slice(&bytes))
+    }
+
+    fn into_token(self) -> Token {
+        Token::FixedBytes(self.as_bytes().to_vec())
+    }
+
+    fn is_valid_operator(&self) -> bool {
+        ed25519_dalek::PublicKey::from_bytes(self.as_bytes()).is_ok()
+    }
+
+    fn signer(hash: H256, signature: &[u8]) -> Result<Self, ()> {
+        ed25519_verify(hash, signature)
+    }
+}
+
+#[cfg(feature = "test-utils")]
+thread_local! {
+    /// Signers recovered by the host during the current call
+    static RECOVERY_COUNT: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// It returns the number of signers recovered by the host during the current call
+///
+/// Returns:
+///
+/// The number of `ecrecover` host calls.
+#[cfg(feature = "test-utils")]
+pub fn recovery_count() -> u64 {
+    RECOVERY_COUNT.with(|count| count.get())
+}
+
+/// It compares two byte slices in constant time, so the comparison of hashes and addresses does not
+/// depend on the position of their first difference
+///
+/// Arguments:
+///
+/// * `a`: The first bytes.
+/// * `b`: The second bytes.
+///
+/// Returns:
+///
+/// Whether the bytes are equal, `false` if their lengths differ.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
 }
 
 /// It takes a slice of bytes and returns a 32-byte hash
@@ -67,6 +248,80 @@ where
     hash
 }
 
+/// `Keccak256Hasher` computes a Keccak-256 hash incrementally, so large payloads can be hashed while
+/// they are being built instead of being concatenated first. The result is the same as `keccak256`
+/// of the concatenation of all the chunks.
+#[derive(Default)]
+pub struct Keccak256Hasher {
+    hasher: Keccak256,
+}
+
+impl Keccak256Hasher {
+    /// `new` creates a hasher with no data
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `update` feeds a chunk of bytes to the hasher
+    ///
+    /// Arguments:
+    ///
+    /// * `bytes`: The next chunk of the data to hash.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    /// `finalize` consumes the hasher and returns the hash of all the chunks
+    ///
+    /// Returns:
+    ///
+    /// A 32 byte array
+    pub fn finalize(self) -> [u8; 32] {
+        self.hasher.finalize().into()
+    }
+}
+
+/// It prefixes a 32-byte hash as an Ethereum signed message (EIP-191) and hashes it again, which is
+/// the hash the operators sign for a command batch
+///
+/// Arguments:
+///
+/// * `data_hash`: The keccak256 hash of the signed data.
+///
+/// Returns:
+///
+/// The keccak256 hash of `"\x19Ethereum Signed Message:\n32"` followed by `data_hash`.
+pub fn to_eth_signed_message_hash(data_hash: [u8; 32]) -> [u8; 32] {
+    const PREFIX: &str = "\x19Ethereum Signed Message:\n32";
+
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(PREFIX.as_bytes());
+    hasher.update(&data_hash);
+    hasher.finalize()
+}
+
+/// The domain of the operatorship transfers signed by the current operators.
+pub const DOMAIN_OPERATORSHIP_TRANSFER: &[u8] = b"axelar-near-operatorship-transfer";
+
+/// It hashes the data under a domain and prefixes it as an Ethereum signed message, so a proof
+/// signed for one domain can not be replayed in another
+///
+/// Arguments:
+///
+/// * `domain`: The domain the data is signed for, such as `DOMAIN_OPERATORSHIP_TRANSFER`.
+/// * `data`: The signed data.
+///
+/// Returns:
+///
+/// The Ethereum signed message hash of the keccak256 hash of `keccak256(domain)` followed by `data`.
+pub fn to_domain_message_hash(domain: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256Hasher::new();
+    hasher.update(&keccak256(domain));
+    hasher.update(data);
+
+    to_eth_signed_message_hash(hasher.finalize())
+}
+
 /// It takes a byte array and a list of expected output types, and returns a list of tokens
 ///
 /// Arguments:
@@ -76,12 +331,12 @@ where
 ///
 /// Returns:
 ///
-/// A vector of tokens.
-pub fn abi_decode(data: &[u8], expected_output_types: &[ParamType]) -> Result<Vec<Token>, String> {
-    match decode(expected_output_types, data) {
-        Ok(tokens) => Ok(tokens),
-        Err(e) => Err(format!("Error decoding ABI-encoded data: {:?}", e)),
-    }
+/// A vector of tokens, or the error of the decoder if the data does not match the types.
+pub fn abi_decode(
+    data: &[u8],
+    expected_output_types: &[ParamType],
+) -> Result<Vec<Token>, ethabi::Error> {
+    decode(expected_output_types, data)
 }
 
 /// It takes a vector of tokens and returns a vector of bytes
@@ -94,7 +349,154 @@ pub fn abi_decode(data: &[u8], expected_output_types: &[ParamType]) -> Result<Ve
 ///
 /// A vector of bytes.
 pub fn abi_encode(tokens: Vec<Token>) -> Vec<u8> {
-    encode(&tokens)
+    abi_encode_ref(&tokens)
+}
+
+/// It ABI encodes borrowed tokens the same way as `abi_encode`, so tokens that are still needed
+/// afterwards do not have to be cloned
+///
+/// Arguments:
+///
+/// * `tokens`: A slice of tokens to encode.
+///
+/// Returns:
+///
+/// A vector of bytes.
+pub fn abi_encode_ref(tokens: &[Token]) -> Vec<u8> {
+    encode(tokens)
+}
+
+/// It computes the absolute threshold of a fraction of the total weight, rounded up so the fraction
+/// is always reached
+///
+/// Arguments:
+///
+/// * `total_weight`: The total weight of the operators.
+/// * `numerator`: The numerator of the fraction.
+/// * `denominator`: The denominator of the fraction.
+///
+/// Returns:
+///
+/// The smallest weight that is at least the fraction of the total weight.
+pub fn fractional_threshold(total_weight: u64, numerator: u64, denominator: u64) -> U256 {
+    let threshold = (u128::from(total_weight) * u128::from(numerator)).div_ceil(denominator.into());
+
+    U256::from(threshold)
+}
+
+/// It checks whether an address can be registered as an operator
+///
+/// Arguments:
+///
+/// * `address`: The operator address.
+///
+/// Returns:
+///
+/// A boolean value, `false` for the zero address.
+pub fn is_valid_operator_address(address: &H160) -> bool {
+    !address.is_zero()
+}
+
+/// It ABI encodes the operators, weights and threshold in the layout `transfer_operatorship` decodes
+/// and the operators hash is computed from
+///
+/// Arguments:
+///
+/// * `operators`: The operator addresses, or the public keys of Ed25519 operators.
+/// * `weights`: The weight of each operator.
+/// * `threshold`: The minimum weight required to validate a proof.
+///
+/// Returns:
+///
+/// The ABI encoded `(address[], uint256[], uint256)` tuple, `(bytes32[], uint256[], uint256)` for
+/// Ed25519 operators.
+pub fn encode_operators<K: OperatorKey>(
+    operators: &[K],
+    weights: &[U256],
+    threshold: U256,
+) -> Vec<u8> {
+    abi_encode(vec![
+        Token::Array(operators.iter().copied().map(K::into_token).collect()),
+        Token::Array(weights.iter().copied().map(Token::Uint).collect()),
+        Token::Uint(threshold),
+    ])
+}
+
+/// It hashes the ABI encoded operators, weights and threshold of an operator set. The key type is
+/// prepended to the params of the operators other than secp256k1, so their hash never matches the
+/// hash of secp256k1 params, which stays the same as on the EVM gateways.
+///
+/// Arguments:
+///
+/// * `key_type`: The type of the keys of the operators.
+/// * `params`: The ABI encoded operators, weights and threshold.
+///
+/// Returns:
+///
+/// The keccak256 hash of the params, tagged with the key type unless it is `Secp256k1`.
+pub fn operators_hash(key_type: KeyType, params: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256Hasher::new();
+
+    if key_type != KeyType::Secp256k1 {
+        hasher.update(&[key_type as u8]);
+    }
+
+    hasher.update(params);
+    hasher.finalize()
+}
+
+/// It hashes the operators, weights and threshold of an ABI encoded proof straight from its bytes,
+/// as the `(address[], uint256[], uint256)` params they were registered with, instead of encoding
+/// the decoded tokens again. The head of the params is rebuilt from the head of the proof, which
+/// has one more word for the signatures offset, and the rest is the proof up to the signatures.
+///
+/// Arguments:
+///
+/// * `key_type`: The type of the keys of the operators, tagged in the hash like `operators_hash`.
+/// * `proof`: The ABI encoded `(address[], uint256[], uint256, bytes[])` proof, the operators are
+///   `bytes32` for Ed25519 and have the same layout.
+///
+/// Returns:
+///
+/// The keccak256 hash of the params, or `None` if the operators or weights are not encoded before
+/// the signatures. A proof that is not canonically encoded hashes to unknown operators.
+pub fn operators_hash_from_proof(key_type: KeyType, proof: &[u8]) -> Option<[u8; 32]> {
+    const WORD: usize = 32;
+    const PROOF_HEAD: usize = 4 * WORD;
+
+    let offset = |index: usize| -> Option<usize> {
+        let word = proof.get(index * WORD..(index + 1) * WORD)?;
+        let offset = U256::from_big_endian(word);
+
+        if offset > U256::from(proof.len()) || offset < U256::from(PROOF_HEAD) {
+            return None;
+        }
+
+        Some(offset.as_usize())
+    };
+
+    let operators_offset = offset(0)?;
+    let weights_offset = offset(1)?;
+    let signatures_offset = offset(3)?;
+
+    let params_offset = |offset: usize| -> [u8; WORD] {
+        let mut word = [0u8; WORD];
+        U256::from(offset - WORD).to_big_endian(&mut word);
+        word
+    };
+
+    let mut hasher = Keccak256Hasher::new();
+
+    if key_type != KeyType::Secp256k1 {
+        hasher.update(&[key_type as u8]);
+    }
+
+    hasher.update(&params_offset(operators_offset));
+    hasher.update(&params_offset(weights_offset));
+    hasher.update(&proof[2 * WORD..3 * WORD]);
+    hasher.update(&proof[PROOF_HEAD..signatures_offset]);
+
+    Some(hasher.finalize())
 }
 
 /// It takes a string, removes the first two characters, and then converts the remaining string into a
@@ -112,6 +514,20 @@ pub fn clean_payload(payload: String) -> Vec<u8> {
     hex::decode(clean_payload).unwrap()
 }
 
+/// It takes a string, removes the `0x` prefix, and then converts the remaining string into a vector of
+/// bytes without panicking
+///
+/// Arguments:
+///
+/// * `payload`: The payload of the transaction.
+///
+/// Returns:
+///
+/// A vector of bytes, or `None` if the payload is not a `0x` prefixed hex string
+pub fn try_clean_payload(payload: String) -> Option<Vec<u8>> {
+    hex::decode(payload.strip_prefix("0x")?).ok()
+}
+
 /// It takes a string, removes the first two characters, and then converts the remaining string into a
 /// 256-bit hash
 ///
@@ -139,3 +555,143 @@ pub fn to_h256(payload: String) -> H256 {
 pub fn to_eth_hex_string(payload: [u8; 32]) -> String {
     format!("0x{}", hex::encode(payload))
 }
+
+/// `EthAddress` is an `H160` that is serialized as a `0x` prefixed hex string, as `H160` has no
+/// JSON representation in the public methods
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthAddress(pub H160);
+
+impl From<H160> for EthAddress {
+    fn from(address: H160) -> Self {
+        EthAddress(address)
+    }
+}
+
+impl From<EthAddress> for H160 {
+    fn from(address: EthAddress) -> Self {
+        address.0
+    }
+}
+
+impl BorshSerialize for EthAddress {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0 .0, writer)
+    }
+}
+
+impl BorshDeserialize for EthAddress {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(EthAddress(H160(
+            <[u8; 20] as BorshDeserialize>::deserialize(buf)?,
+        )))
+    }
+}
+
+impl BorshSchema for EthAddress {
+    fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+        Self::add_definition(
+            Self::declaration(),
+            Definition::Struct {
+                fields: Fields::UnnamedFields(vec![<[u8; 20]>::declaration()]),
+            },
+            definitions,
+        );
+        <[u8; 20]>::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        "EthAddress".to_string()
+    }
+}
+
+impl Serialize for EthAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:#x}", self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for EthAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = <String as Deserialize>::deserialize(deserializer)?;
+
+        match try_clean_payload(address) {
+            Some(bytes) if bytes.len() == 20 => Ok(EthAddress(H160::from_slice(&bytes))),
+            _ => Err(de::Error::custom("Invalid address")),
+        }
+    }
+}
+
+/// `Weight` is a `U256` that is serialized as a decimal string, as a JSON number can not hold it.
+/// It is deserialized from either a decimal string or a JSON number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Weight(pub U256);
+
+impl From<U256> for Weight {
+    fn from(weight: U256) -> Self {
+        Weight(weight)
+    }
+}
+
+impl From<Weight> for U256 {
+    fn from(weight: Weight) -> Self {
+        weight.0
+    }
+}
+
+impl BorshSerialize for Weight {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut bytes = [0u8; 32];
+        self.0.to_little_endian(&mut bytes);
+        BorshSerialize::serialize(&bytes, writer)
+    }
+}
+
+impl BorshDeserialize for Weight {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let bytes = <[u8; 32] as BorshDeserialize>::deserialize(buf)?;
+
+        Ok(Weight(U256::from_little_endian(&bytes)))
+    }
+}
+
+// The weight is stored as its 32 little endian bytes
+impl BorshSchema for Weight {
+    fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+        Self::add_definition(
+            Self::declaration(),
+            Definition::Struct {
+                fields: Fields::UnnamedFields(vec![<[u8; 32]>::declaration()]),
+            },
+            definitions,
+        );
+        <[u8; 32]>::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        "Weight".to_string()
+    }
+}
+
+impl Serialize for Weight {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Weight {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(crate = "near_sdk::serde", untagged)]
+        enum WeightRepr {
+            Number(u64),
+            Decimal(String),
+        }
+
+        match WeightRepr::deserialize(deserializer)? {
+            WeightRepr::Number(weight) => Ok(Weight(weight.into())),
+            WeightRepr::Decimal(weight) => U256::from_dec_str(&weight)
+                .map(Weight)
+                .map_err(|_| de::Error::custom("Invalid weight")),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Snapshot of the Borsh layout of the contract state, for the off-chain decoders of the state.
+//! After an intended layout change, regenerate it with
+//! `UPDATE_BORSH_SCHEMA=1 cargo test --test borsh_schema` in the `/contract` folder.
+use axelar_cgp_near::schema::{layout_matches_state, state_schema};
+use near_sdk::serde_json;
+use std::{env, fs};
+
+const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/borsh_schema.json");
+
+#[test]
+fn layout_mirrors_the_contract_state() {
+    assert!(
+        layout_matches_state(),
+        "`AxelarLayout` does not match the fields of `Axelar`"
+    );
+}
+
+#[test]
+fn schema_matches_the_snapshot() {
+    let schema = serde_json::to_string_pretty(&state_schema()).unwrap() + "\n";
+
+    if env::var_os("UPDATE_BORSH_SCHEMA").is_some() {
+        fs::write(SNAPSHOT_PATH, &schema).unwrap();
+    }
+
+    let snapshot = fs::read_to_string(SNAPSHOT_PATH).unwrap_or_default();
+
+    assert!(
+        schema == snapshot,
+        "The Borsh layout of the state changed, regenerate `borsh_schema.json` with \
+         `UPDATE_BORSH_SCHEMA=1 cargo test --test borsh_schema` if the change is intended:\n{}",
+        schema
+    );
+}
@@ -0,0 +1,47 @@
+//! Constant-time comparison of hashes and addresses, checked against `==`.
+use axelar_cgp_near::ct_eq;
+
+const HASH: [u8; 32] = [0x5a; 32];
+
+#[test]
+fn equal_bytes_are_equal() {
+    let other = HASH;
+
+    assert!(ct_eq(&HASH, &other));
+    assert!(ct_eq(&[], &[]));
+    assert_eq!(ct_eq(&HASH, &other), HASH == other);
+}
+
+#[test]
+fn bytes_differing_at_any_position_are_not_equal() {
+    for index in 0..HASH.len() {
+        let mut other = HASH;
+        other[index] ^= 1;
+
+        assert!(!ct_eq(&HASH, &other));
+        assert_eq!(ct_eq(&HASH, &other), HASH == other);
+    }
+}
+
+#[test]
+fn bytes_of_different_lengths_are_not_equal() {
+    assert!(!ct_eq(&HASH, &HASH[..20]));
+    assert!(!ct_eq(&HASH[..20], &HASH));
+    assert!(!ct_eq(&HASH, &[]));
+}
+
+#[test]
+fn comparison_matches_the_equality_operator() {
+    let hashes = [[0u8; 32], [0xff; 32], HASH, {
+        let mut hash = HASH;
+        hash[31] = 0;
+        hash
+    }];
+
+    for a in &hashes {
+        for b in &hashes {
+            assert_eq!(ct_eq(a, b), a == b);
+            assert_eq!(ct_eq(&a[..20], &b[..20]), a[..20] == b[..20]);
+        }
+    }
+}
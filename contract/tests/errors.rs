@@ -0,0 +1,54 @@
+//! Conversions of the ABI decoding errors into the errors reported by the contract.
+use axelar_cgp_near::errors::{AuthError, ProofError};
+use std::borrow::Cow;
+
+#[test]
+fn invalid_data_converts_to_invalid_abi_encoding() {
+    let error = AuthError::from(ethabi::Error::InvalidData);
+
+    assert_eq!(
+        error,
+        AuthError::InvalidAbiEncoding("Invalid data".to_string())
+    );
+    assert_eq!(
+        error.to_string(),
+        "Malformed operators params: Invalid data"
+    );
+}
+
+#[test]
+fn invalid_name_converts_to_invalid_abi_encoding() {
+    let error = AuthError::from(ethabi::Error::InvalidName("operators".to_string()));
+
+    assert_eq!(
+        error,
+        AuthError::InvalidAbiEncoding("Invalid name operators".to_string())
+    );
+    assert_eq!(
+        error.to_string(),
+        "Malformed operators params: Invalid name operators"
+    );
+}
+
+#[test]
+fn other_converts_to_invalid_abi_encoding() {
+    let error = AuthError::from(ethabi::Error::Other(Cow::Borrowed("Unexpected length")));
+
+    assert_eq!(
+        error,
+        AuthError::InvalidAbiEncoding("Unexpected length".to_string())
+    );
+    assert_eq!(
+        error.to_string(),
+        "Malformed operators params: Unexpected length"
+    );
+}
+
+#[test]
+fn decoding_errors_convert_to_malformed_proof() {
+    let error = ProofError::from(ethabi::Error::InvalidData);
+
+    assert_eq!(error, ProofError::MalformedProof);
+    assert_eq!(error.to_string(), "Malformed proof");
+    assert_eq!(error.reason(), "malformed_proof");
+}
@@ -0,0 +1,13 @@
+#!/bin/sh
+
+./build.sh
+
+if [ $? -ne 0 ]; then
+  echo ">> Error building contract"
+  exit 1
+fi
+
+echo ">> Deploying contract"
+
+# https://docs.near.org/tools/near-cli#near-dev-deploy
+near dev-deploy --wasmFile ./target/wasm32-unknown-unknown/release/axelar_gas_service_near.wasm
@@ -0,0 +1,66 @@
+use near_contract_tools::event;
+use near_sdk::json_types::U128;
+
+/// `NativeGasPaidForContractCallEvent` is emitted when the gas of a contract call is paid.
+///
+/// Properties:
+///
+/// * `source_address`: The account that is making the contract call.
+/// * `destination_chain`: The chain that the contract call is being made to.
+/// * `destination_address`: The address of the contract that will receive the call.
+/// * `payload_hash`: The hash of the payload.
+/// * `gas_fee_amount`: The amount of yoctoNEAR paid for the gas.
+/// * `refund_address`: The account that is refunded the unused gas.
+#[event(standard = "axelar_near", version = "1.0.0")]
+pub struct NativeGasPaidForContractCallEvent {
+    pub source_address: String,
+    pub destination_chain: String,
+    pub destination_address: String,
+    pub payload_hash: String,
+    pub gas_fee_amount: U128,
+    pub refund_address: String,
+}
+
+/// `NativeGasAddedEvent` is emitted when gas is added to an already sent contract call.
+///
+/// Properties:
+///
+/// * `tx_hash`: The hash of the transaction of the contract call.
+/// * `log_index`: The index of the contract call event in the transaction.
+/// * `gas_fee_amount`: The amount of yoctoNEAR added for the gas.
+/// * `refund_address`: The account that is refunded the unused gas.
+#[event(standard = "axelar_near", version = "1.0.0")]
+pub struct NativeGasAddedEvent {
+    pub tx_hash: String,
+    pub log_index: u64,
+    pub gas_fee_amount: U128,
+    pub refund_address: String,
+}
+
+/// `RefundedEvent` is emitted when overpaid gas is returned to a user.
+///
+/// Properties:
+///
+/// * `tx_hash`: The hash of the transaction the gas was paid in.
+/// * `log_index`: The index of the gas payment event in the transaction.
+/// * `receiver`: The account that receives the refund.
+/// * `amount`: The amount of yoctoNEAR refunded.
+#[event(standard = "axelar_near", version = "1.0.0")]
+pub struct RefundedEvent {
+    pub tx_hash: String,
+    pub log_index: u64,
+    pub receiver: String,
+    pub amount: U128,
+}
+
+/// `FeesCollectedEvent` is emitted when the owner withdraws the earned fees.
+///
+/// Properties:
+///
+/// * `receiver`: The account that receives the fees.
+/// * `amount`: The amount of yoctoNEAR collected.
+#[event(standard = "axelar_near", version = "1.0.0")]
+pub struct FeesCollectedEvent {
+    pub receiver: String,
+    pub amount: U128,
+}
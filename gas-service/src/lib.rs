@@ -0,0 +1,204 @@
+/*
+ * Axelar Gas Service contract
+ *
+ */
+
+mod events;
+
+use axelar_executable_near::utils::{clean_payload, keccak256, to_eth_hex_string};
+use events::{
+    FeesCollectedEvent, NativeGasAddedEvent, NativeGasPaidForContractCallEvent, RefundedEvent,
+};
+use near_contract_tools::standard::nep297::Event;
+use near_contract_tools::{owner::*, Owner};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, PanicOnDefault, Promise};
+
+/// `AxelarGasService` collects the gas that is prepaid for contract calls, relayers are reimbursed
+/// from it.
+///
+/// Properties:
+///
+/// * `total_gas_paid`: The total amount of yoctoNEAR paid for gas.
+#[near_bindgen]
+#[derive(Owner, PanicOnDefault, BorshDeserialize, BorshSerialize)]
+pub struct AxelarGasService {
+    total_gas_paid: Balance,
+}
+
+#[near_bindgen]
+impl AxelarGasService {
+    /// `new` is called when the contract is first deployed, the caller becomes the owner
+    ///
+    /// Returns:
+    ///
+    /// The contract is being returned.
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self { total_gas_paid: 0 };
+
+        Owner::init(&mut contract, &env::predecessor_account_id());
+
+        contract
+    }
+
+    /// `total_gas_paid` returns the total amount paid for gas
+    ///
+    /// Returns:
+    ///
+    /// The amount in yoctoNEAR.
+    pub fn total_gas_paid(&self) -> U128 {
+        self.total_gas_paid.into()
+    }
+
+    /// It records the attached deposit as the gas of a contract call, and emits a
+    /// `NativeGasPaidForContractCallEvent` event
+    ///
+    /// Arguments:
+    ///
+    /// * `sender`: The account that is making the contract call.
+    /// * `destination_chain`: The chain that the contract call is being made to.
+    /// * `destination_address`: The address of the contract that will receive the call.
+    /// * `payload`: The payload of the contract call.
+    /// * `refund_address`: The account that is refunded the unused gas.
+    ///
+    /// Returns:
+    ///
+    /// The emitted event.
+    #[payable]
+    pub fn pay_gas_for_contract_call(
+        &mut self,
+        sender: AccountId,
+        destination_chain: String,
+        destination_address: String,
+        payload: String,
+        refund_address: AccountId,
+    ) -> NativeGasPaidForContractCallEvent {
+        let gas_fee_amount = env::attached_deposit();
+
+        require!(gas_fee_amount > 0, "Nothing received");
+
+        self.total_gas_paid += gas_fee_amount;
+
+        let event = NativeGasPaidForContractCallEvent {
+            source_address: sender.to_string(),
+            destination_chain,
+            destination_address,
+            payload_hash: to_eth_hex_string(keccak256(clean_payload(payload))),
+            gas_fee_amount: gas_fee_amount.into(),
+            refund_address: refund_address.to_string(),
+        };
+
+        Event::emit(&event);
+
+        event
+    }
+
+    /// It records the attached deposit as additional gas for an already sent contract call, and
+    /// emits a `NativeGasAddedEvent` event
+    ///
+    /// Arguments:
+    ///
+    /// * `tx_hash`: The hash of the transaction of the contract call.
+    /// * `log_index`: The index of the contract call event in the transaction.
+    /// * `refund_address`: The account that is refunded the unused gas.
+    ///
+    /// Returns:
+    ///
+    /// The emitted event.
+    #[payable]
+    pub fn add_native_gas(
+        &mut self,
+        tx_hash: String,
+        log_index: u64,
+        refund_address: AccountId,
+    ) -> NativeGasAddedEvent {
+        let gas_fee_amount = env::attached_deposit();
+
+        require!(gas_fee_amount > 0, "Nothing received");
+
+        self.total_gas_paid += gas_fee_amount;
+
+        let event = NativeGasAddedEvent {
+            tx_hash,
+            log_index,
+            gas_fee_amount: gas_fee_amount.into(),
+            refund_address: refund_address.to_string(),
+        };
+
+        Event::emit(&event);
+
+        event
+    }
+
+    // Only owner
+
+    /// `refund` returns overpaid gas to the user that paid it
+    ///
+    /// Arguments:
+    ///
+    /// * `tx_hash`: The hash of the transaction the gas was paid in.
+    /// * `log_index`: The index of the gas payment event in the transaction.
+    /// * `receiver`: The account that receives the refund.
+    /// * `amount`: The amount of yoctoNEAR to refund.
+    #[payable]
+    pub fn refund(
+        &mut self,
+        tx_hash: String,
+        log_index: u64,
+        receiver: AccountId,
+        amount: U128,
+    ) -> Promise {
+        Self::require_owner();
+
+        let event = RefundedEvent {
+            tx_hash,
+            log_index,
+            receiver: receiver.to_string(),
+            amount,
+        };
+
+        Event::emit(&event);
+
+        Self::internal_transfer(receiver, amount.into())
+    }
+
+    /// `collect_fees` withdraws the fees earned by the relayers
+    ///
+    /// Arguments:
+    ///
+    /// * `receiver`: The account that receives the fees.
+    /// * `amount`: The amount of yoctoNEAR to collect.
+    #[payable]
+    pub fn collect_fees(&mut self, receiver: AccountId, amount: U128) -> Promise {
+        Self::require_owner();
+
+        let event = FeesCollectedEvent {
+            receiver: receiver.to_string(),
+            amount,
+        };
+
+        Event::emit(&event);
+
+        Self::internal_transfer(receiver, amount.into())
+    }
+}
+
+impl AxelarGasService {
+    /// It transfers yoctoNEAR out of the contract, keeping the balance locked for storage
+    ///
+    /// Arguments:
+    ///
+    /// * `receiver`: The account that receives the transfer.
+    /// * `amount`: The amount of yoctoNEAR to transfer.
+    fn internal_transfer(receiver: AccountId, amount: Balance) -> Promise {
+        let storage_balance = Balance::from(env::storage_usage()) * env::storage_byte_cost();
+        let available_balance = env::account_balance().saturating_sub(storage_balance);
+
+        require!(amount > 0, "Nothing to transfer");
+        require!(amount <= available_balance, "Insufficient balance");
+
+        Promise::new(receiver).transfer(amount)
+    }
+}
@@ -0,0 +1,193 @@
+import { SignerWithAddress } from "@nomiclabs/hardhat-ethers/signers";
+import anyTest, { TestFn } from "ava";
+import { sortBy } from "lodash";
+import { NearAccount, Worker } from "near-workspaces";
+import path from "path";
+import Utils from "./utils";
+const { ethers } = require("hardhat");
+
+const test = anyTest as TestFn<{
+  worker: Worker;
+  accounts: Record<string, NearAccount>;
+}>;
+
+const TGAS = 1e12;
+
+// Upper bounds of the gas burnt by `validate_proof` for each number of signers, every operator
+// signs with a weight of 1 and the threshold is the number of operators
+const VALIDATE_PROOF_GAS_LIMITS: Record<number, number> = {
+  1: 6 * TGAS,
+  5: 8 * TGAS,
+  10: 11 * TGAS,
+  20: 16 * TGAS,
+  50: 32 * TGAS,
+};
+
+// The gas of an additional signer for the largest sets must stay within this factor of the gas of
+// an additional signer for the smallest sets, so the signers are matched in linear time
+const MARGINAL_GAS_TOLERANCE = 1.5;
+
+// The hardhat accounts are not enough for the largest operator set
+const getOperators = (count: number) =>
+  sortBy(
+    Array.from(
+      { length: count },
+      (_, i) => new ethers.Wallet(ethers.utils.id(`operator-${i}`))
+    ),
+    (wallet) => wallet.address.toLowerCase()
+  ) as unknown as SignerWithAddress[];
+
+test.beforeEach(async (t) => {
+  const worker = await Worker.init();
+
+  const root = worker.rootAccount;
+
+  const contract = await root.createSubAccount("axelar_cgp_near");
+
+  await contract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  await root.call(contract, "new", { recent_operators: [] });
+
+  t.context.worker = worker;
+  t.context.accounts = { root, contract };
+});
+
+test.afterEach.always(async (t) => {
+  await t.context.worker.tearDown().catch((error) => {
+    console.log("Failed to stop the Sandbox:", error);
+  });
+});
+
+test("Gas - validate_proof stays within the gas limits for each operator set size", async (t) => {
+  const { root, contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const gasBurnt: Record<number, number> = {};
+
+  for (const size of Object.keys(VALIDATE_PROOF_GAS_LIMITS).map(Number)) {
+    const operators = getOperators(size);
+    const weights = operators.map(() => 1);
+
+    await root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(operators),
+          weights,
+          size
+        ),
+      },
+      { attachedDeposit: "0" }
+    );
+
+    const tx = await root.callRaw(
+      contract,
+      "validate_proof",
+      {
+        message_hash: message,
+        proof: await Utils.getWeightedSignaturesProof(
+          data,
+          operators,
+          weights,
+          size,
+          operators
+        ),
+      },
+      { gas: "300000000000000" }
+    );
+
+    t.true(tx.parseResult());
+
+    gasBurnt[size] = tx.result.receipts_outcome
+      .map((receipt) => receipt.outcome.gas_burnt)
+      .reduce((a, b) => a + b, 0);
+
+    t.log(
+      `validate_proof gas burnt for ${size} signers: ${(
+        gasBurnt[size] / TGAS
+      ).toFixed(2)} TGas`
+    );
+
+    t.true(
+      gasBurnt[size] <= VALIDATE_PROOF_GAS_LIMITS[size],
+      `${size} signers burnt ${gasBurnt[size]} gas, over the limit of ${VALIDATE_PROOF_GAS_LIMITS[size]}`
+    );
+  }
+
+  const smallSignerGas = (gasBurnt[10] - gasBurnt[1]) / 9;
+  const largeSignerGas = (gasBurnt[50] - gasBurnt[20]) / 30;
+
+  t.true(
+    largeSignerGas <= smallSignerGas * MARGINAL_GAS_TOLERANCE,
+    `An additional signer burnt ${largeSignerGas} gas for the largest sets, and ${smallSignerGas} gas for the smallest sets`
+  );
+});
+
+test("Gas - validate_proof does not depend on the position of the signers among the operators", async (t) => {
+  const { root, contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const operators = getOperators(50);
+  const weights = operators.map(() => 1);
+  const signerCount = 5;
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        weights,
+        signerCount
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const getGasBurnt = async (signers: SignerWithAddress[]) => {
+    const tx = await root.callRaw(
+      contract,
+      "validate_proof",
+      {
+        message_hash: message,
+        proof: await Utils.getWeightedSignaturesProof(
+          data,
+          operators,
+          weights,
+          signerCount,
+          signers
+        ),
+      },
+      { gas: "300000000000000" }
+    );
+
+    t.true(tx.parseResult());
+
+    return tx.result.receipts_outcome
+      .map((receipt) => receipt.outcome.gas_burnt)
+      .reduce((a, b) => a + b, 0);
+  };
+
+  const firstSignersGas = await getGasBurnt(operators.slice(0, signerCount));
+  const lastSignersGas = await getGasBurnt(operators.slice(-signerCount));
+
+  t.log(
+    `validate_proof gas burnt for the first and last ${signerCount} of 50 operators: ${firstSignersGas} and ${lastSignersGas}`
+  );
+
+  // The signers are matched by binary search, skipping over operators is not paid for
+  t.true(lastSignersGas <= firstSignersGas * 1.05);
+});
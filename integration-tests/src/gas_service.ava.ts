@@ -0,0 +1,260 @@
+import anyTest, { TestFn } from "ava";
+import { NEAR, NearAccount, Worker } from "near-workspaces";
+import path from "path";
+const { ethers } = require("hardhat");
+
+const test = anyTest as TestFn<{
+  worker: Worker;
+  accounts: Record<string, NearAccount>;
+}>;
+
+test.beforeEach(async (t) => {
+  const worker = await Worker.init();
+
+  const root = worker.rootAccount;
+
+  const john = await root.createSubAccount("john", {
+    initialBalance: NEAR.parse("3 N").toJSON(),
+  });
+
+  const gasService = await root.createSubAccount("axelar_gas_service");
+
+  await gasService.deploy(
+    path.join(
+      __dirname,
+      "../../gas-service/target/wasm32-unknown-unknown/release/axelar_gas_service_near.wasm"
+    )
+  );
+
+  await root.call(gasService, "new", {});
+
+  t.context.worker = worker;
+  t.context.accounts = { root, gasService, john };
+});
+
+test.afterEach.always(async (t) => {
+  await t.context.worker.tearDown().catch((error) => {
+    console.log("Failed to stop the Sandbox:", error);
+  });
+});
+
+test("Gas Service - should record the gas paid for a contract call", async (t) => {
+  const { gasService, john } = t.context.accounts;
+
+  const chain = "Polygon";
+  const destination = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["string"],
+    ["Hello from NEAR!"]
+  );
+  const gasFeeAmount = NEAR.parse("1 N").toString();
+
+  const tx = await john.callRaw(
+    gasService,
+    "pay_gas_for_contract_call",
+    {
+      sender: john.accountId,
+      destination_chain: chain,
+      destination_address: destination,
+      payload,
+      refund_address: john.accountId,
+    },
+    { attachedDeposit: gasFeeAmount }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "native_gas_paid_for_contract_call_event");
+  t.deepEqual(events[0].data, {
+    source_address: john.accountId,
+    destination_chain: chain,
+    destination_address: destination,
+    payload_hash: ethers.utils.keccak256(payload),
+    gas_fee_amount: gasFeeAmount,
+    refund_address: john.accountId,
+  });
+
+  t.is(await gasService.view("total_gas_paid", {}), gasFeeAmount);
+});
+
+test("Gas Service - should not accept a payment without a deposit", async (t) => {
+  const { gasService, john } = t.context.accounts;
+
+  const error = await t.throwsAsync(
+    john.call(gasService, "pay_gas_for_contract_call", {
+      sender: john.accountId,
+      destination_chain: "Polygon",
+      destination_address: "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+      payload: "0x",
+      refund_address: john.accountId,
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Nothing received"));
+});
+
+test("Gas Service - should add gas to an already sent contract call", async (t) => {
+  const { gasService, john } = t.context.accounts;
+
+  const txHash = ethers.utils.keccak256("0x123abc123abc");
+  const gasFeeAmount = NEAR.parse("0.5 N").toString();
+
+  const tx = await john.callRaw(
+    gasService,
+    "add_native_gas",
+    {
+      tx_hash: txHash,
+      log_index: 2,
+      refund_address: john.accountId,
+    },
+    { attachedDeposit: gasFeeAmount }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "native_gas_added_event");
+  t.deepEqual(events[0].data, {
+    tx_hash: txHash,
+    log_index: 2,
+    gas_fee_amount: gasFeeAmount,
+    refund_address: john.accountId,
+  });
+
+  t.is(await gasService.view("total_gas_paid", {}), gasFeeAmount);
+});
+
+const payGas = async (
+  gasService: NearAccount,
+  payer: NearAccount,
+  gasFeeAmount: string
+) =>
+  payer.call(
+    gasService,
+    "pay_gas_for_contract_call",
+    {
+      sender: payer.accountId,
+      destination_chain: "Polygon",
+      destination_address: "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+      payload: "0x123abc",
+      refund_address: payer.accountId,
+    },
+    { attachedDeposit: gasFeeAmount }
+  );
+
+test("Gas Service - should refund overpaid gas", async (t) => {
+  const { gasService, john, root } = t.context.accounts;
+
+  await payGas(gasService, john, NEAR.parse("1 N").toString());
+
+  const txHash = ethers.utils.keccak256("0x123abc123abc");
+  const amount = NEAR.parse("0.5 N");
+
+  const balanceBefore = await john.availableBalance();
+
+  const tx = await root.callRaw(
+    gasService,
+    "refund",
+    {
+      tx_hash: txHash,
+      log_index: 1,
+      receiver: john.accountId,
+      amount: amount.toString(),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "refunded_event");
+  t.deepEqual(events[0].data, {
+    tx_hash: txHash,
+    log_index: 1,
+    receiver: john.accountId,
+    amount: amount.toString(),
+  });
+
+  const balanceAfter = await john.availableBalance();
+
+  t.true(balanceAfter.sub(balanceBefore).eq(amount));
+});
+
+test("Gas Service - should not withdraw more than the available balance", async (t) => {
+  const { gasService, john, root } = t.context.accounts;
+
+  await payGas(gasService, john, NEAR.parse("1 N").toString());
+
+  const { amount: accountBalance } = await gasService.accountView();
+
+  const error = await t.throwsAsync(
+    root.call(
+      gasService,
+      "collect_fees",
+      { receiver: root.accountId, amount: accountBalance },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Insufficient balance"));
+});
+
+test("Gas Service - should collect fees to the receiver", async (t) => {
+  const { gasService, john, root } = t.context.accounts;
+
+  const alice = await root.createSubAccount("alice");
+
+  await payGas(gasService, john, NEAR.parse("1 N").toString());
+
+  const amount = NEAR.parse("1 N");
+
+  const balanceBefore = await alice.availableBalance();
+
+  await root.call(
+    gasService,
+    "collect_fees",
+    { receiver: alice.accountId, amount: amount.toString() },
+    { attachedDeposit: "0" }
+  );
+
+  const balanceAfter = await alice.availableBalance();
+
+  t.true(balanceAfter.sub(balanceBefore).eq(amount));
+});
+
+test("Gas Service - should not allow a non-owner to collect fees", async (t) => {
+  const { gasService, john } = t.context.accounts;
+
+  await payGas(gasService, john, NEAR.parse("1 N").toString());
+
+  const error = await t.throwsAsync(
+    john.call(gasService, "collect_fees", {
+      receiver: john.accountId,
+      amount: NEAR.parse("1 N").toString(),
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+});
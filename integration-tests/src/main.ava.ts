@@ -1,11 +1,14 @@
 import { SignerWithAddress } from "@nomiclabs/hardhat-ethers/signers";
 import anyTest, { TestFn } from "ava";
 import { sortBy } from "lodash";
+import fs from "fs";
 import { NEAR, NearAccount, Worker } from "near-workspaces";
 import path from "path";
 import Utils from "./utils";
 const { ethers } = require("hardhat");
 
+type TransferResult = { epoch: number; operators_hash: string };
+
 const test = anyTest as TestFn<{
   worker: Worker;
   accounts: Record<string, NearAccount>;
@@ -50,6 +53,51 @@ const initContract = async (root: NearAccount, contract: NearAccount) => {
   });
 };
 
+const TOKEN_WASM = path.join(
+  __dirname,
+  "../../token/target/wasm32-unknown-unknown/release/axelar_token_near.wasm"
+);
+
+const setTokenCode = async (root: NearAccount, contract: NearAccount) => {
+  const code = fs.readFileSync(TOKEN_WASM);
+
+  await root.call(
+    contract,
+    "set_token_code",
+    { code: code.toString("base64") },
+    { gas: "300000000000000" }
+  );
+};
+
+const executeCommands = async (
+  root: NearAccount,
+  contract: NearAccount,
+  commandNames: string[],
+  commands: string[]
+) => {
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    commands.map(() => Utils.getRandomID()),
+    commandNames,
+    commands
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  return root.callRaw(
+    contract,
+    "execute",
+    { input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+};
+
 test.before(async (t) => {
   wallets = await ethers.getSigners();
   wallets = wallets;
@@ -159,11 +207,19 @@ test("Auth - reject the proof if weights are not matching the threshold", async
     ),
   };
 
-  const error = await t.throwsAsync(contract.view("validate_proof", args));
+  // A well-formed proof below the threshold is rejected without aborting the call
+  t.is(await contract.view("validate_proof", args), false);
+  t.is(await contract.view("can_validate_proof", args), "insufficient_weight");
 
-  // t.log(error?.message); // uncomment to see the error message
+  // The rejection is logged by the view, as it returns normally
+  const events = (await contract.viewRaw("validate_proof", args)).logs
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
 
-  t.not(error, undefined); // Low signature weight
+  t.is(events.length, 1);
+  t.is(events[0].event, "proof_validation_failed_event");
+  t.is(events[0].data.message_hash, message);
+  t.is(events[0].data.reason, "invalid_signatures");
 });
 
 test("Auth - reject the proof if signatures are invalid", async (t) => {
@@ -186,11 +242,9 @@ test("Auth - reject the proof if signatures are invalid", async (t) => {
     ),
   };
 
-  const error = await t.throwsAsync(contract.view("validate_proof", args));
-
-  // t.log(error?.message); // uncomment to see the error message
-
-  t.not(error, undefined); // Malformed signers
+  // Signers that are not the operators are rejected without aborting the call
+  t.is(await contract.view("validate_proof", args), false);
+  t.is(await contract.view("can_validate_proof", args), "invalid_signatures");
 });
 
 test("Auth - validate the proof from the recent operators", async (t) => {
@@ -226,6 +280,221 @@ test("Auth - validate the proof from the recent operators", async (t) => {
   );
 });
 
+test("Auth - return the epoch of the operators that signed the proof", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const getProof = (signers: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      signers,
+      signers.map(() => 1),
+      threshold,
+      signers.slice(0, threshold)
+    );
+
+  t.deepEqual(
+    await contract.view("validate_proof_epoch", {
+      message_hash: message,
+      proof: await getProof(operators),
+    }),
+    { is_current_operators: true, operators_epoch: currentEpoch }
+  );
+
+  // The previous operators were registered in order, right before the current operators
+  const retainedOperators = previousOperators[previousOperators.length - 2];
+
+  t.deepEqual(
+    await contract.view("validate_proof_epoch", {
+      message_hash: message,
+      proof: await getProof(retainedOperators),
+    }),
+    { is_current_operators: false, operators_epoch: currentEpoch - 2 }
+  );
+});
+
+test("Auth - validate a proof with the legacy boolean view", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const getProof = (signers: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      signers,
+      signers.map(() => 1),
+      threshold,
+      signers.slice(0, threshold)
+    );
+
+  t.true(
+    await contract.view("validate_proof_legacy", {
+      message_hash: message,
+      proof: await getProof(operators),
+    })
+  );
+  t.false(
+    await contract.view("validate_proof_legacy", {
+      message_hash: message,
+      proof: await getProof(previousOperators[previousOperators.length - 1]),
+    })
+  );
+
+  // The errors of `validate_proof` make the legacy view panic
+  const error = await t.throwsAsync(
+    contract.view("validate_proof_legacy", {
+      message_hash: message,
+      proof: await getProof(previousOperators[0]),
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid epoch"));
+});
+
+test("Auth - validate a proof submitted with the registered operators params", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  // The previous operators were registered in order, right before the current operators
+  const retainedOperators = previousOperators[previousOperators.length - 2];
+
+  for (const [signers, operatorsEpoch] of [
+    [operators, currentEpoch],
+    [retainedOperators, currentEpoch - 2],
+  ] as [SignerWithAddress[], number][]) {
+    const weights = signers.map(() => 1);
+
+    const signatures = await Utils.getSignatures(
+      data,
+      signers.slice(0, threshold)
+    );
+
+    t.deepEqual(
+      await contract.view("validate_proof_epoch", {
+        message_hash: message,
+        proof: Utils.getWeightedProof(
+          Utils.getAddresses(signers),
+          weights,
+          threshold,
+          signatures
+        ),
+      }),
+      {
+        is_current_operators: operatorsEpoch === currentEpoch,
+        operators_epoch: operatorsEpoch,
+      }
+    );
+
+    t.is(
+      await contract.view("validate_proof_with_params", {
+        message_hash: message,
+        operators_params: await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(signers),
+          weights,
+          threshold
+        ),
+        signatures,
+      }),
+      operatorsEpoch === currentEpoch
+    );
+  }
+
+  // The same operators with another threshold were never registered
+  const error = await contract
+    .view("validate_proof_with_params", {
+      message_hash: message,
+      operators_params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold - 1
+      ),
+      signatures: await Utils.getSignatures(
+        data,
+        operators.slice(0, threshold)
+      ),
+    })
+    .catch((e) => e);
+
+  t.true(error?.message.includes("Unknown operators"));
+});
+
+test("Auth - emit the epoch of the operators that signed a validated proof", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const getEvents = async (signers: SignerWithAddress[]) => {
+    const tx = await root.callRaw(
+      contract,
+      "validate_proof_logged",
+      {
+        message_hash: message,
+        proof: await Utils.getWeightedSignaturesProof(
+          data,
+          signers,
+          signers.map(() => 1),
+          threshold,
+          signers.slice(0, threshold)
+        ),
+      },
+      { attachedDeposit: "0" }
+    );
+
+    t.false(tx.failed);
+
+    return tx.result.receipts_outcome
+      .flatMap((receipt) => receipt.outcome.logs)
+      .filter((log) => log.includes("axelar_near"))
+      .map((log) => JSON.parse(log.slice(11)));
+  };
+
+  let events = await getEvents(operators);
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "proof_validated_event");
+  t.deepEqual(events[0].data, {
+    message_hash: message,
+    operators_epoch: currentEpoch,
+    is_current: true,
+  });
+
+  events = await getEvents(previousOperators[previousOperators.length - 1]);
+
+  t.is(events.length, 1);
+  t.deepEqual(events[0].data, {
+    message_hash: message,
+    operators_epoch: currentEpoch - 1,
+    is_current: false,
+  });
+});
+
 test("Auth - reject the proof from the operators older than key retention", async (t) => {
   const { contract } = t.context.accounts;
 
@@ -257,30 +526,16 @@ test("Auth - reject the proof from the operators older than key retention", asyn
 
       // t.log(error?.message); // uncomment to see the error message
 
-      t.not(error, undefined); // Invalid operators
+      t.not(error, undefined);
+      t.true(error?.message.includes("Invalid epoch"));
     })
   );
 });
 
-test("Auth - validate the proof for a single operator", async (t) => {
+test("Auth - reject the proof with more signatures than the maximum", async (t) => {
   const { contract, root } = t.context.accounts;
 
-  const singleOperator = Utils.getAddresses([owner]);
-
-  const didTransferOperatorship = await root.call(
-    contract,
-    "transfer_operatorship",
-    {
-      params: await Utils.getTransferWeightedOperatorshipCommand(
-        singleOperator,
-        [1],
-        1
-      ),
-    },
-    { attachedDeposit: "0" }
-  );
-
-  t.is(didTransferOperatorship, true);
+  t.is(await contract.view("max_signatures", {}), 64);
 
   const data = "0x123abc123abc";
 
@@ -288,351 +543,392 @@ test("Auth - validate the proof for a single operator", async (t) => {
     ethers.utils.arrayify(ethers.utils.keccak256(data))
   );
 
-  let args = {
-    message_hash: message,
-    proof: await Utils.getWeightedSignaturesProof(data, [owner], [1], 1, [
-      owner,
-    ]),
-  };
+  // Unrecoverable signatures, the proof must be rejected before recovering any of them
+  const getProof = (count: number) =>
+    Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold,
+      Array(count).fill(ethers.utils.hexZeroPad("0x", 65))
+    );
 
-  const isCurrentOperators = await contract.view("validate_proof", args);
+  let error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(65),
+    })
+  );
 
-  t.is(isCurrentOperators, true);
-});
+  // t.log(error?.message); // uncomment to see the error message
 
-test("Auth - validate the proof for a single signer", async (t) => {
-  const { contract, root } = t.context.accounts;
+  t.not(error, undefined);
+  t.true(error?.message.includes("Too many signatures"));
 
-  const didTransferOperatorship = await root.call(
+  await root.call(
     contract,
-    "transfer_operatorship",
-    {
-      params: await Utils.getTransferWeightedOperatorshipCommand(
-        Utils.getAddresses(operators),
-        operators.map(() => 1),
-        1
-      ),
-    },
+    "set_max_signatures",
+    { max_signatures: 2 },
     { attachedDeposit: "0" }
   );
 
-  t.is(didTransferOperatorship, true);
+  error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(3),
+    })
+  );
 
-  const data = "0x123abc123abc";
+  t.true(error?.message.includes("Too many signatures"));
 
-  const message = ethers.utils.hashMessage(
-    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(2),
+    })
   );
 
-  let args = {
-    message_hash: message,
-    proof: await Utils.getWeightedSignaturesProof(
-      data,
-      operators,
-      operators.map(() => 1),
-      1,
-      operators.slice(0, 1)
-    ),
-  };
+  t.true(error?.message.includes("Invalid signature"));
+});
 
-  const isCurrentOperators = await contract.view("validate_proof", args);
+test("Auth - reject the proof from unknown operators", async (t) => {
+  const { contract } = t.context.accounts;
 
-  t.is(isCurrentOperators, true);
-});
+  const data = "0x123abc123abc";
 
-test("Auth - should allow owner to transfer operatorship", async (t) => {
-  const { contract, root } = t.context.accounts;
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
 
-  const newOperators = [
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
-  ];
+  // Every seeded operator set has at least three operators
+  const unknownOperators = sortBy(wallets.slice(0, 2), (wallet) =>
+    wallet.address.toLowerCase()
+  );
 
-  const didTransferOperatorship = await root.call(
-    contract,
-    "transfer_operatorship",
-    {
-      params: await Utils.getTransferWeightedOperatorshipCommand(
-        newOperators,
-        [1, 1],
-        2
+  const error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        unknownOperators,
+        unknownOperators.map(() => 1),
+        2,
+        unknownOperators
       ),
-    },
-    { attachedDeposit: "0" }
+    })
   );
 
-  t.is(didTransferOperatorship, true);
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Unknown operators"));
 });
 
-test("Auth - should not allow transferring operatorship to address zero", async (t) => {
-  const { contract, root } = t.context.accounts;
+test("Auth - report the status of a proof without validating it", async (t) => {
+  const { contract } = t.context.accounts;
 
-  const newOperators = [
-    ADDRESS_ZERO,
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-  ];
+  const data = "0x123abc123abc";
 
-  const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1, 1],
-          2
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
   );
 
-  // t.log(error?.message); // uncomment to see the error message
+  const getStatus = async (
+    operators: SignerWithAddress[],
+    signers: SignerWithAddress[]
+  ) =>
+    contract.view("can_validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        signers
+      ),
+    });
 
-  t.not(error, undefined); // Invalid operators
-});
+  const retainedOperators = previousOperators[previousOperators.length - 1];
+  const expiredOperators = previousOperators[0];
 
-test("Auth - should not allow transferring operatorship to duplicated operators", async (t) => {
-  const { contract, root } = t.context.accounts;
+  // Every seeded operator set has at least three operators
+  const unknownOperators = sortBy(wallets.slice(0, 2), (wallet) =>
+    wallet.address.toLowerCase()
+  );
 
-  const newOperators = [
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-  ];
-
-  const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1, 1],
-          2
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
+  t.is(
+    await getStatus(operators, operators.slice(0, threshold)),
+    "valid_current"
+  );
+  t.is(
+    await getStatus(retainedOperators, retainedOperators.slice(0, threshold)),
+    "valid_retained"
+  );
+  t.is(
+    await getStatus(expiredOperators, expiredOperators.slice(0, threshold)),
+    "expired_epoch"
+  );
+  t.is(
+    await getStatus(unknownOperators, unknownOperators),
+    "unknown_operators"
+  );
+  t.is(
+    await getStatus(operators, operators.slice(0, threshold - 1)),
+    "insufficient_weight"
+  );
+  t.is(
+    await getStatus(operators, wallets.slice(0, threshold)),
+    "invalid_signatures"
   );
 
-  // t.log(error?.message); // uncomment to see the error message
+  const proof = await Utils.getWeightedSignaturesProof(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
 
-  t.not(error, undefined); // Invalid operators
+  t.is(
+    await contract.view("can_validate_proof", {
+      message_hash: message,
+      proof: ethers.utils.hexDataSlice(proof, 0, 64),
+    }),
+    "malformed"
+  );
+  t.is(
+    await contract.view("can_validate_proof", {
+      message_hash: message,
+      proof: "not a proof",
+    }),
+    "malformed"
+  );
+  t.is(
+    await contract.view("can_validate_proof", {
+      message_hash: "0x1234",
+      proof,
+    }),
+    "malformed"
+  );
 });
 
-test("Auth - should not allow transferring operatorship to unsorted operators", async (t) => {
-  const { contract, root } = t.context.accounts;
+test("Auth - decode a proof into its operators, weights and threshold", async (t) => {
+  const { contract } = t.context.accounts;
 
-  const newOperators = [
-    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-  ];
+  const data = "0x123abc123abc";
 
-  const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await ethers.utils.defaultAbiCoder.encode(
-          ["address[]", "uint256[]", "uint256"],
-          [newOperators, [1, 1], 2]
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
-  );
+  const weights = operators.map((_, i) => i + 1);
 
-  // t.log(error?.message); // uncomment to see the error message
+  t.deepEqual(
+    await contract.view("decode_proof", {
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        weights,
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    }),
+    {
+      operators: Utils.getAddresses(operators).map((address) =>
+        address.toLowerCase()
+      ),
+      weights: weights.map(String),
+      threshold: String(threshold),
+      signature_count: threshold,
+    }
+  );
 
-  t.not(error, undefined); // Invalid operators
+  // Malformed proofs are reported as `null` instead of panicking
+  for (const proof of [
+    "not hex",
+    "0x",
+    "0x1234",
+    `0x${"ff".repeat(160)}`,
+    ethers.utils.defaultAbiCoder.encode(["uint256"], [1]),
+  ]) {
+    t.is(await contract.view("decode_proof", { proof }), null);
+  }
 });
 
-test("Auth - should not allow operatorship transfer to the previous operators", async (t) => {
-  const { contract, root } = t.context.accounts;
+test("Auth - reject the proof with a malleable high-s signature", async (t) => {
+  const { contract } = t.context.accounts;
 
-  const updatedOperators = Utils.getAddresses(operators.slice(0, threshold));
+  const data = "0x123abc123abc";
 
-  const didTransferOperatorship = await root.call(
-    contract,
-    "transfer_operatorship",
-    {
-      params: await Utils.getTransferWeightedOperatorshipCommand(
-        updatedOperators,
-        updatedOperators.map(() => 1),
-        threshold
-      ),
-    },
-    { attachedDeposit: "0" }
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
   );
 
-  t.is(didTransferOperatorship, true);
+  const signatures = await Utils.getSignatures(
+    data,
+    operators.slice(0, threshold)
+  );
 
-  const oldOperators = Utils.getAddresses(operators);
+  const getProof = (signatures: string[]) =>
+    Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold,
+      signatures
+    );
 
   const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          oldOperators,
-          oldOperators.map(() => 1),
-          threshold
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof([
+        Utils.getHighSSignature(signatures[0]),
+        ...signatures.slice(1),
+      ]),
+    })
   );
 
   // t.log(error?.message); // uncomment to see the error message
 
-  t.not(error, undefined); // Duplicate operators
-});
-
-test("Auth - should not allow transferring operatorship with invalid threshold", async (t) => {
-  const { contract, root } = t.context.accounts;
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid signature"));
 
-  const newOperators = [
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
-  ];
+  const isCurrentOperators = await contract.view("validate_proof", {
+    message_hash: message,
+    proof: getProof(signatures),
+  });
 
-  let error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1, 1],
-          0
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
-  );
+  t.is(isCurrentOperators, true);
+});
 
-  // t.log(error?.message); // uncomment to see the error message
+test("Auth - validate the proof with raw and ethereum recovery bytes", async (t) => {
+  const { contract } = t.context.accounts;
 
-  t.not(error, undefined); // Invalid threshold
+  const data = "0x123abc123abc";
 
-  error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1, 1],
-          3
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
   );
 
-  // t.log(error?.message); // uncomment to see the error message
-
-  t.not(error, undefined); // Invalid threshold
-});
-
-test("Auth - should not allow transferring operatorship with invalid number of weights", async (t) => {
-  const { contract, root } = t.context.accounts;
+  const signatures = await Utils.getSignatures(
+    data,
+    operators.slice(0, threshold)
+  );
 
-  const newOperators = [
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
-  ];
+  const getProof = (signatures: string[]) =>
+    Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold,
+      signatures
+    );
 
-  let error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1],
-          0
-        ),
-      },
-      { attachedDeposit: "0" }
+  const rawSignatures = signatures.map((signature) =>
+    Utils.getSignatureWithRecoveryByte(
+      signature,
+      ethers.utils.splitSignature(signature).recoveryParam
     )
   );
 
-  // t.log(error?.message); // uncomment to see the error message
+  t.is(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(signatures),
+    }),
+    true
+  );
 
-  t.not(error, undefined); // Invalid weights
+  t.is(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(rawSignatures),
+    }),
+    true
+  );
 
-  error = await t.throwsAsync(
-    root.call(
-      contract,
-      "transfer_operatorship",
-      {
-        params: await Utils.getTransferWeightedOperatorshipCommand(
-          newOperators,
-          [1, 1, 1],
-          3
-        ),
-      },
-      { attachedDeposit: "0" }
-    )
+  const error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof([
+        Utils.getSignatureWithRecoveryByte(signatures[0], 2),
+        ...signatures.slice(1),
+      ]),
+    })
   );
 
   // t.log(error?.message); // uncomment to see the error message
 
-  t.not(error, undefined); // Invalid weights
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid signature"));
 });
 
-test("Auth - should expose correct hashes and epoch", async (t) => {
+test("Auth - validate the proof with compact signatures", async (t) => {
   const { contract } = t.context.accounts;
 
-  const operatorsHistory = [...previousOperators, operators];
+  const data = "0x123abc123abc";
 
-  await Promise.all(
-    operatorsHistory.map(async (operators, i) => {
-      const payload = await Utils.getTransferWeightedOperatorshipCommand(
-        Utils.getAddresses(operators),
-        operators.map(() => 1),
-        threshold
-      );
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
 
-      const hash = ethers.utils.keccak256(payload);
+  const signatures = await Utils.getSignatures(
+    data,
+    operators.slice(0, threshold)
+  );
 
-      const hashForEpoch = await contract.view("hash_for_epoch", {
-        epoch: i + 1,
-      });
+  const getProof = (signatures: string[]) =>
+    Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold,
+      signatures
+    );
 
-      t.is(hashForEpoch, hash);
+  const compactSignatures = signatures.map(Utils.getCompactSignature);
 
-      const epochForHash = await contract.view("epoch_for_hash", { hash });
+  t.true(
+    compactSignatures.every(
+      (signature) => ethers.utils.hexDataLength(signature) === 64
+    )
+  );
 
-      t.is(epochForHash, i + 1);
+  t.is(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof(compactSignatures),
+    }),
+    true
+  );
+
+  // The same signers are recovered from both forms, which can be mixed in a proof
+  t.is(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof([compactSignatures[0], ...signatures.slice(1)]),
+    }),
+    true
+  );
+
+  const error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: getProof([
+        ethers.utils.hexDataSlice(signatures[0], 0, 63),
+        ...signatures.slice(1),
+      ]),
     })
   );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid signature"));
 });
 
-// Gateway Tests
+test("Auth - reject a truncated proof as malformed", async (t) => {
+  const { contract } = t.context.accounts;
 
-test("Gateway - should fail if chain id mismatches", async (t) => {
-  const { contract, root } = t.context.accounts;
+  const data = "0x123abc123abc";
 
-  const data = await Utils.buildCommandBatch(
-    CHAIN_ID + 1,
-    [Utils.getRandomID()],
-    ["transferOperatorship"],
-    [
-      await Utils.getTransferWeightedOperatorshipCommand(
-        [
-          "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
-          "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-        ],
-        [1, 1],
-        2
-      ),
-    ]
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
   );
 
-  const input = await Utils.getSignedWeightedExecuteInput(
+  const proof = await Utils.getWeightedSignaturesProof(
     data,
     operators,
     operators.map(() => 1),
@@ -641,98 +937,5024 @@ test("Gateway - should fail if chain id mismatches", async (t) => {
   );
 
   const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "execute",
-      {
-        input,
-      },
-      { attachedDeposit: "0" }
-    )
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: ethers.utils.hexDataSlice(proof, 0, 64),
+    })
   );
 
   // t.log(error?.message); // uncomment to see the error message
 
-  t.not(error, undefined); // Invalid chain id
+  t.not(error, undefined);
+  t.true(error?.message.includes("Malformed proof"));
 });
 
-test("Gateway - should not allow transferring operatorship to address zero", async (t) => {
-  const { contract, root } = t.context.accounts;
+test("Auth - validate signatures against the threshold without the epoch", async (t) => {
+  const { contract } = t.context.accounts;
 
-  const newOperators = [
-    ADDRESS_ZERO,
-    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
-  ];
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const validateSignatures = async (signers: SignerWithAddress[]) =>
+    contract.view("validate_signatures", {
+      message_hash: message,
+      operators: Utils.getAddresses(operators),
+      weights: operators.map(() => 1),
+      threshold,
+      signatures: await Utils.getSignatures(data, signers),
+    });
+
+  t.is(await validateSignatures(operators.slice(0, threshold)), true);
+  t.is(await validateSignatures(operators.slice(0, threshold - 1)), false);
+  t.is(await validateSignatures(operators.slice(0, threshold + 1)), true);
+});
+
+test("Auth - validate signatures returns false for signers that are not operators", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const isValid = await contract.view("validate_signatures", {
+    message_hash: message,
+    operators: Utils.getAddresses(operators),
+    weights: operators.map(() => 1),
+    threshold,
+    signatures: await Utils.getSignatures(data, wallets.slice(0, threshold)),
+  });
+
+  t.is(isValid, false);
+});
+
+test("Auth - recover the EVM address of known signing keys", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const vectors = [
+    {
+      privateKey:
+        "0x0123456789012345678901234567890123456789012345678901234567890123",
+      address: "0x14791697260E4c9A71f18484C9f997B308e59325",
+    },
+    {
+      privateKey:
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+      address: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    },
+  ];
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  for (const { privateKey, address } of vectors) {
+    const wallet = new ethers.Wallet(privateKey);
+
+    t.is(wallet.address, address);
+
+    const signature = await wallet.signMessage(
+      ethers.utils.arrayify(ethers.utils.keccak256(data))
+    );
+
+    t.true(
+      await contract.view("validate_signatures", {
+        message_hash: message,
+        operators: [address],
+        weights: [1],
+        threshold: 1,
+        signatures: [signature],
+      })
+    );
+
+    t.false(
+      await contract.view("validate_signatures", {
+        message_hash: message,
+        operators: [ADDRESS_ZERO.replace(/0$/, "1")],
+        weights: [1],
+        threshold: 1,
+        signatures: [signature],
+      })
+    );
+  }
+});
+
+test("Auth - compute the EVM address of known public keys", async (t) => {
+  const { contract } = t.context.accounts;
+
+  // The public keys of the private keys 1 and 2
+  const vectors = [
+    {
+      publicKey:
+        "0x0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+      address: "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+    },
+    {
+      publicKey:
+        "0x04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+      address: "0x2b5ad5c4795c026514f8317c7a215e218dccd6cf",
+    },
+  ];
+
+  for (const operator of operators) {
+    const message = "operator";
+
+    vectors.push({
+      publicKey: ethers.utils.recoverPublicKey(
+        ethers.utils.hashMessage(message),
+        await operator.signMessage(message)
+      ),
+      address: operator.address.toLowerCase(),
+    });
+  }
+
+  for (const { publicKey, address } of vectors) {
+    t.is(
+      await contract.view("address_for_public_key", { public_key: publicKey }),
+      address
+    );
+
+    // Without the uncompressed prefix
+    t.is(
+      await contract.view("address_for_public_key", {
+        public_key: ethers.utils.hexDataSlice(publicKey, 1),
+      }),
+      address
+    );
+  }
+
+  const compressedPublicKey = ethers.utils.computePublicKey(
+    vectors[0].publicKey,
+    true
+  );
+
+  t.is(
+    await contract.view("address_for_public_key", {
+      public_key: compressedPublicKey,
+    }),
+    null
+  );
+});
+
+test("Auth - reject the proof with duplicated or unordered signatures", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const signatures = await Utils.getSignatures(
+    data,
+    operators.slice(0, threshold)
+  );
+
+  const getProof = (signatures: string[]) =>
+    Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold,
+      signatures
+    );
+
+  const invalidSignatureSets = [
+    [signatures[0], signatures[0], signatures[1]],
+    [signatures[1], signatures[0], signatures[2]],
+  ];
+
+  for (const invalidSignatures of invalidSignatureSets) {
+    const error = await t.throwsAsync(
+      contract.view("validate_proof", {
+        message_hash: message,
+        proof: getProof(invalidSignatures),
+      })
+    );
+
+    // t.log(error?.message); // uncomment to see the error message
+
+    t.not(error, undefined);
+    t.true(error?.message.includes("Duplicate or unordered signer"));
+  }
+});
+
+test("Auth - match the signers anywhere in a large operator set", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const largeOperators = sortBy(wallets.slice(0, 20), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const sign = async (signer: SignerWithAddress) =>
+    (await Utils.getSignatures(data, [signer]))[0];
+
+  // The signatures are kept in the given order, they are not sorted by signer
+  const validateSignatures = async (
+    signers: SignerWithAddress[],
+    operators = largeOperators
+  ) =>
+    contract.view("validate_signatures", {
+      message_hash: message,
+      operators: Utils.getAddresses(operators),
+      weights: operators.map(() => 1),
+      threshold: 2,
+      signatures: await Promise.all(signers.map(sign)),
+    });
+
+  const [first, last] = [largeOperators[0], largeOperators[19]];
+
+  t.is(await validateSignatures([first, last]), true);
+  t.is(await validateSignatures([largeOperators[18], last]), true);
+  t.is(await validateSignatures([largeOperators[3], largeOperators[11]]), true);
+  t.is(await validateSignatures([last, first]), false);
+  t.is(await validateSignatures([largeOperators[7], largeOperators[7]]), false);
+  const nonOperator = new ethers.Wallet(ethers.utils.id("non-operator"));
+
+  t.is(await validateSignatures([first, nonOperator]), false);
+  t.is(await validateSignatures([last]), false);
+
+  // The operators must be sorted for the signers to be matched
+  t.is(
+    await validateSignatures([last, first], [...largeOperators].reverse()),
+    false
+  );
+});
+
+test("Auth - validate the proof for a single operator", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const singleOperator = Utils.getAddresses([owner]);
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        singleOperator,
+        [1],
+        1
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  let args = {
+    message_hash: message,
+    proof: await Utils.getWeightedSignaturesProof(data, [owner], [1], 1, [
+      owner,
+    ]),
+  };
+
+  const isCurrentOperators = await contract.view("validate_proof", args);
+
+  t.is(isCurrentOperators, true);
+});
+
+test("Auth - register a single weighted operator and validate its proof against the threshold", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const singleOperator = new ethers.Wallet(
+    ethers.utils.id("single-operator")
+  ) as unknown as SignerWithAddress;
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses([singleOperator]),
+        [5],
+        3
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+  t.deepEqual(await contract.view("current_operators", {}), {
+    operators: [singleOperator.address.toLowerCase()],
+    weights: ["5"],
+    threshold: "3",
+  });
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.true(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        [singleOperator],
+        [5],
+        3,
+        [singleOperator]
+      ),
+    })
+  );
+
+  // Without the signature of the single operator the threshold is not reached
+  t.false(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        [singleOperator],
+        [5],
+        3,
+        []
+      ),
+    })
+  );
+});
+
+test("Auth - validate the proof for a single signer", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        1
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  let args = {
+    message_hash: message,
+    proof: await Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      1,
+      operators.slice(0, 1)
+    ),
+  };
+
+  const isCurrentOperators = await contract.view("validate_proof", args);
+
+  t.is(isCurrentOperators, true);
+});
+
+test("Auth - validate the proof for operators with weights near u32 max", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const U32_MAX = 4294967295;
+
+  const largeOperators = sortBy(wallets.slice(0, 2), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+  const largeWeights = [U32_MAX, 2];
+
+  // The total weight wraps around to 1 when accumulated in 32 bits
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(largeOperators),
+        largeWeights,
+        U32_MAX
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const isCurrentOperators = await contract.view("validate_proof", {
+    message_hash: message,
+    proof: await Utils.getWeightedSignaturesProof(
+      data,
+      largeOperators,
+      largeWeights,
+      U32_MAX,
+      largeOperators
+    ),
+  });
+
+  t.is(isCurrentOperators, true);
+});
+
+test("Auth - should allow owner to transfer operatorship", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        newOperators,
+        [1, 1],
+        2
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+});
+
+test("Auth - should return the epoch and hash of the new operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    { params },
+    { attachedDeposit: "0" }
+  );
+
+  t.deepEqual(transferResult, {
+    epoch: currentEpoch + 1,
+    operators_hash: ethers.utils.keccak256(params),
+  });
+  t.is(await contract.view("current_epoch", {}), transferResult.epoch);
+  t.is(
+    await contract.view("hash_for_epoch", { epoch: transferResult.epoch }),
+    transferResult.operators_hash
+  );
+  t.is(
+    await contract.view("epoch_for_hash", {
+      hash: transferResult.operators_hash,
+    }),
+    transferResult.epoch
+  );
+});
+
+test("Auth - should register a fractional threshold as an absolute threshold", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  // 6667 basis points of the total weight, rounded up
+  const cases = [
+    { weights: [1, 1, 1], threshold: 3 },
+    { weights: [1, 2, 3], threshold: 5 },
+    { weights: [1, 2, 3, 4], threshold: 7 },
+    { weights: [10, 20, 30, 40], threshold: 67 },
+  ];
+
+  for (const [index, { weights, threshold }] of cases.entries()) {
+    // Operators that are not seeded, a seeded set with the same threshold would be a duplicate
+    const newOperators = sortBy(
+      weights.map(
+        (_, i) =>
+          new ethers.Wallet(ethers.utils.id(`fractional-operator-${index}-${i}`))
+      ),
+      (wallet) => wallet.address.toLowerCase()
+    ) as unknown as SignerWithAddress[];
+
+    const tx = await root.callRaw(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(newOperators),
+          weights,
+          Utils.getFractionalThreshold(6667)
+        ),
+      },
+      { attachedDeposit: "0" }
+    );
+
+    const transferResult: TransferResult = tx.parseResult();
+
+    // The operators are registered with the absolute threshold their proofs carry
+    t.is(
+      transferResult.operators_hash,
+      ethers.utils.keccak256(
+        await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(newOperators),
+          weights,
+          threshold
+        )
+      )
+    );
+
+    const event = tx.result.receipts_outcome
+      .flatMap((receipt) => receipt.outcome.logs)
+      .filter((log) => log.includes("axelar_near"))
+      .map((log) => JSON.parse(log.slice(11)))[0];
+
+    t.is(event.data.new_threshold, threshold);
+    t.is(event.data.epoch, transferResult.epoch);
+
+    t.true(
+      await contract.view("validate_proof", {
+        message_hash: message,
+        proof: await Utils.getWeightedSignaturesProof(
+          data,
+          newOperators,
+          weights,
+          threshold,
+          newOperators
+        ),
+      })
+    );
+  }
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  for (const basisPoints of [0, 10001]) {
+    const error = await t.throwsAsync(
+      root.call(
+        contract,
+        "transfer_operatorship",
+        {
+          params: await Utils.getTransferWeightedOperatorshipCommand(
+            newOperators,
+            [1, 1],
+            Utils.getFractionalThreshold(basisPoints)
+          ),
+        },
+        { attachedDeposit: "0" }
+      )
+    );
+
+    t.true(error?.message.includes("Invalid threshold"));
+  }
+});
+
+test("Auth - should allow transferring operatorship to new operators twice", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const seededEpochs = previousOperators.length + 1;
+
+  const newOperatorSets = [
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    [
+      "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+      "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+    ],
+  ];
+
+  for (const [i, newOperators] of newOperatorSets.entries()) {
+    const params = await Utils.getTransferWeightedOperatorshipCommand(
+      newOperators,
+      newOperators.map(() => 1),
+      1
+    );
+
+    const transferResult: TransferResult = await root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    );
+
+    t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+
+    const epochForHash = await contract.view("epoch_for_hash", {
+      hash: ethers.utils.keccak256(params),
+    });
+
+    t.is(epochForHash, seededEpochs + i + 1);
+  }
+});
+
+test("Auth - should emit the new epoch when transferring operatorship", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  const tx = await root.callRaw(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        newOperators,
+        [1, 1],
+        2
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  const currentEpoch = await contract.view("current_epoch", {});
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "operatorship_transferred_event");
+  t.is(events[0].data.epoch, currentEpoch);
+  t.is(events[0].data.new_threshold, 2);
+});
+
+test("Auth - should allow transferring operatorship through several sets in a batch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const seededEpochs = previousOperators.length + 1;
+
+  const newOperatorSets = [
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    ["0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88"],
+    [
+      "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+      "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+    ],
+  ];
+
+  const paramsList = await Promise.all(
+    newOperatorSets.map((newOperators) =>
+      Utils.getTransferWeightedOperatorshipCommand(
+        newOperators,
+        newOperators.map(() => 1),
+        1
+      )
+    )
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "transfer_operatorship_batch",
+    { params_list: paramsList },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(await contract.view("current_epoch", {}), seededEpochs + 3);
+
+  const transferResults: TransferResult[] = tx.parseResult();
+
+  t.deepEqual(
+    transferResults,
+    paramsList.map((params, i) => ({
+      epoch: seededEpochs + i + 1,
+      operators_hash: ethers.utils.keccak256(params),
+    }))
+  );
+
+  t.is(events.length, 3);
+
+  for (const [i, newOperators] of newOperatorSets.entries()) {
+    t.is(events[i].event, "operatorship_transferred_event");
+    t.is(events[i].data.epoch, seededEpochs + i + 1);
+    t.deepEqual(
+      events[i].data.new_operators,
+      newOperators.map((operator) => operator.toLowerCase())
+    );
+  }
+});
+
+test("Auth - should revert the whole batch if one of the sets is invalid", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const currentEpoch = await contract.view("current_epoch", {});
+
+  const paramsList = [
+    await Utils.getTransferWeightedOperatorshipCommand(
+      ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+      [1],
+      1
+    ),
+    await Utils.getTransferWeightedOperatorshipCommand([], [], 1),
+  ];
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship_batch",
+      { params_list: paramsList },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+  t.is(
+    await contract.view("epoch_for_hash", {
+      hash: ethers.utils.keccak256(paramsList[0]),
+    }),
+    null
+  );
+});
+
+test("Auth - should not allow transferring operatorship with truncated params", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    [1],
+    1
+  );
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params: ethers.utils.hexDataSlice(params, 0, 64) },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Malformed operators params"));
+});
+
+test("Auth - should accept the proof and params encoded as base64", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const toBase64 = (hex: string) =>
+    Buffer.from(ethers.utils.arrayify(hex)).toString("base64");
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const getProof = (signers: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      signers
+    );
+
+  t.is(
+    await contract.view("validate_proof_base64", {
+      message_hash: message,
+      proof: toBase64(await getProof(operators.slice(0, threshold))),
+    }),
+    true
+  );
+
+  t.is(
+    await contract.view("validate_proof_base64", {
+      message_hash: message,
+      proof: toBase64(await getProof(operators.slice(0, threshold - 1))),
+    }),
+    false
+  );
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship_base64",
+    { params: toBase64(params) },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.operators_hash, ethers.utils.keccak256(params));
+  t.is(await contract.view("is_current_operators", { params }), true);
+});
+
+test("Auth - should transfer operatorship from typed arguments like the encoded params", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(
+    [
+      "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+      "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+    ],
+    (address) => address.toLowerCase()
+  );
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship_typed",
+    { operators: newOperators, weights: [1, 2], threshold: 2 },
+    { attachedDeposit: "0" }
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    newOperators,
+    [1, 2],
+    2
+  );
+
+  t.is(transferResult.operators_hash, ethers.utils.keccak256(params));
+
+  const epochForHash = await contract.view("epoch_for_hash", {
+    hash: ethers.utils.keccak256(params),
+  });
+
+  t.is(epochForHash, await contract.view("current_epoch", {}));
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Duplicate operators"));
+});
+
+test("Auth - should round-trip the typed addresses and weights through JSON", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 2), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  // The addresses are serialized as lowercase hex, and deserialized back by the typed methods
+  const addresses: string[] = await Promise.all(
+    newOperators.map(async (wallet) =>
+      contract.view("address_for_public_key", {
+        public_key: ethers.utils.recoverPublicKey(
+          ethers.utils.hashMessage("operator"),
+          await wallet.signMessage("operator")
+        ),
+      })
+    )
+  );
+
+  t.deepEqual(
+    addresses,
+    Utils.getAddresses(newOperators).map((address) => address.toLowerCase())
+  );
+
+  // Weights beyond the JSON number range are decimal strings, both forms can be mixed
+  const maxWeight = "4294967295";
+
+  t.true(
+    await root.call(
+      contract,
+      "transfer_operatorship_typed",
+      { operators: addresses, weights: [1, maxWeight], threshold: maxWeight },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    [1, Number(maxWeight)],
+    maxWeight
+  );
+
+  t.is(
+    await contract.view("epoch_for_hash", {
+      hash: ethers.utils.keccak256(params),
+    }),
+    await contract.view("current_epoch", {})
+  );
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship_typed",
+      { operators: addresses, weights: [1, "4294967296"], threshold: 1 },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Invalid weights"));
+
+  for (const args of [
+    { operators: ["0x1234"], weights: [1], threshold: 1 },
+    { operators: addresses, weights: [1, "0x01"], threshold: 1 },
+  ]) {
+    error = await t.throwsAsync(
+      root.call(contract, "transfer_operatorship_typed", args, {
+        attachedDeposit: "0",
+      })
+    );
+
+    t.true(error?.message.includes("Failed to deserialize input from JSON"));
+  }
+});
+
+test("Auth - should hash the typed operators the same way the proof is validated", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 3), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const weights = [1, 2, 3];
+
+  const typedThreshold = 3;
+
+  await root.call(
+    contract,
+    "transfer_operatorship_typed",
+    {
+      operators: Utils.getAddresses(newOperators),
+      weights,
+      threshold: typedThreshold,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    weights,
+    typedThreshold
+  );
+
+  const currentEpoch = await contract.view("current_epoch", {});
+
+  t.is(
+    await contract.view("hash_for_epoch", { epoch: currentEpoch }),
+    ethers.utils.keccak256(params)
+  );
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.true(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        newOperators,
+        weights,
+        typedThreshold,
+        newOperators.slice(1)
+      ),
+    })
+  );
+});
+
+test("Auth - validate the proof from 20 operators against the hash of their params", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 20), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+  const weights = newOperators.map(() => 1);
+  const newThreshold = newOperators.length;
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    weights,
+    newThreshold
+  );
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    { params },
+    { attachedDeposit: "0" }
+  );
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  // The operators hash is computed from the borrowed proof tokens, it must match the stored params
+  const tx = await root.callRaw(
+    contract,
+    "validate_proof",
+    {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        newOperators,
+        weights,
+        newThreshold,
+        newOperators
+      ),
+    },
+    { gas: "300000000000000" }
+  );
+
+  t.true(tx.parseResult());
+
+  t.log(
+    `validate_proof gas burnt for 20 operators: ${tx.result.receipts_outcome
+      .map((receipt) => receipt.outcome.gas_burnt)
+      .reduce((a, b) => a + b, 0)}`
+  );
+});
+
+test("Auth - resolve the epoch of the proof operators from the proof bytes", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+  const weights = [4, 3, 2, 1];
+  const newThreshold = 5;
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(newOperators),
+        weights,
+        newThreshold
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const proof = await Utils.getWeightedSignaturesProof(
+    data,
+    newOperators,
+    weights,
+    newThreshold,
+    newOperators
+  );
+
+  t.true(
+    await contract.view("validate_proof", { message_hash: message, proof })
+  );
+
+  // Once rotated, the same proof resolves to the previous epoch
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+        [1],
+        1
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.false(
+    await contract.view("validate_proof", { message_hash: message, proof })
+  );
+});
+
+test("Auth - transfer the operatorship with a proof of the current operators", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const getProof = (operators: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      Utils.getOperatorshipTransferData(
+        contract.accountId,
+        currentEpoch,
+        params
+      ),
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    );
+
+  // Expired operators can not transfer the operatorship
+  const error = await t.throwsAsync(
+    john.call(
+      contract,
+      "transfer_operatorship_signed",
+      { params, proof: await getProof(previousOperators[0]) },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid epoch"));
+
+  // Any account can submit the proof, it does not need to be the owner
+  const transferResult: TransferResult = await john.call(
+    contract,
+    "transfer_operatorship_signed",
+    { params, proof: await getProof(operators) },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, currentEpoch + 1);
+  t.is(await contract.view("current_epoch", {}), currentEpoch + 1);
+  t.is(await contract.view("is_current_operators", { params }), true);
+});
+
+test("Auth - reject the operatorship transfer signed by retained operators", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  const retainedOperators = previousOperators[previousOperators.length - 1];
+
+  const signedData = Utils.getOperatorshipTransferData(
+    contract.accountId,
+    await contract.view("current_epoch", {}),
+    params
+  );
+
+  const proof = await Utils.getWeightedSignaturesProof(
+    signedData,
+    retainedOperators,
+    retainedOperators.map(() => 1),
+    threshold,
+    retainedOperators.slice(0, threshold)
+  );
+
+  // The proof is valid, but not from the current operators
+  t.is(
+    await contract.view("validate_proof", {
+      message_hash: ethers.utils.hashMessage(
+        ethers.utils.arrayify(ethers.utils.keccak256(signedData))
+      ),
+      proof,
+    }),
+    false
+  );
+
+  const error = await t.throwsAsync(
+    john.call(
+      contract,
+      "transfer_operatorship_signed",
+      { params, proof },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Not the current operators"));
+  t.is(await contract.view("is_current_operators", { params }), false);
+});
+
+test("Auth - reject the operatorship transfer signed for another domain", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  // Signed without a domain, as the command batches are
+  const proof = await Utils.getWeightedSignaturesProof(
+    params,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const error = await t.throwsAsync(
+    john.call(
+      contract,
+      "transfer_operatorship_signed",
+      { params, proof },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Malformed signers"));
+
+  t.is(await contract.view("is_current_operators", { params }), false);
+});
+
+test("Auth - reject a signed operatorship transfer replayed once its operators were revoked", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const getParams = (signers: SignerWithAddress[]) =>
+    Utils.getTransferWeightedOperatorshipCommand(
+      Utils.getAddresses(signers),
+      signers.map(() => 1),
+      threshold
+    );
+
+  const signedOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+  const otherOperators = sortBy(wallets.slice(4, 8), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const params = await getParams(signedOperators);
+  const signedEpoch: number = await contract.view("current_epoch", {});
+
+  const proof = await Utils.getWeightedSignaturesProof(
+    Utils.getOperatorshipTransferData(
+      contract.accountId,
+      signedEpoch,
+      params
+    ),
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const transferResult: TransferResult = await john.call(
+    contract,
+    "transfer_operatorship_signed",
+    { params, proof },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, signedEpoch + 1);
+
+  // The signing operators become current again, and the signed operators are no longer registered
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    { params: await getParams(otherOperators) },
+    { attachedDeposit: "0" }
+  );
+
+  for (const epoch of [signedEpoch + 1, signedEpoch]) {
+    await root.call(
+      contract,
+      "revoke_epoch",
+      { epoch },
+      { attachedDeposit: "0" }
+    );
+  }
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    { params: await getParams(operators) },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(await contract.view("is_current_operators", { params }), false);
+
+  const error = await t.throwsAsync(
+    john.call(
+      contract,
+      "transfer_operatorship_signed",
+      { params, proof },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Malformed signers"));
+  t.is(await contract.view("is_current_operators", { params }), false);
+});
+
+test("Auth - emit the events with the axelar_near standard and version", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(newOperators),
+        newOperators.map(() => 1),
+        threshold
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.startsWith("EVENT_JSON:"))
+    .map((log) => JSON.parse(log.slice(11)))
+    .filter(({ event }) => event === "operatorship_transferred_event");
+
+  t.is(events.length, 1);
+  t.is(events[0].standard, "axelar_near");
+  t.is(events[0].version, "1.1.0");
+});
+
+test("Auth - should not allow transferring operatorship to empty operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand([], [], 1),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Auth - should not allow transferring operatorship to address zero", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    ADDRESS_ZERO,
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+  ];
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1],
+          2
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Auth - should not allow transferring operatorship to address zero among the operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    ADDRESS_ZERO,
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  // Encoded without sorting so that the zero address stays in the middle
+  const params = ethers.utils.defaultAbiCoder.encode(
+    ["address[]", "uint256[]", "uint256"],
+    [newOperators, [1, 1, 1], 2]
+  );
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Auth - should not allow transferring operatorship to duplicated operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+  ];
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1],
+          2
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Auth - should not allow transferring operatorship to unsorted operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+  ];
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await ethers.utils.defaultAbiCoder.encode(
+          ["address[]", "uint256[]", "uint256"],
+          [newOperators, [1, 1], 2]
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Auth - should not allow operatorship transfer to the previous operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const updatedOperators = Utils.getAddresses(operators.slice(0, threshold));
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        updatedOperators,
+        updatedOperators.map(() => 1),
+        threshold
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, await contract.view("current_epoch", {}));
+
+  const oldOperators = Utils.getAddresses(operators);
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          oldOperators,
+          oldOperators.map(() => 1),
+          threshold
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Duplicate operators"));
+});
+
+test("Auth - should not allow transferring operatorship with invalid threshold", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1],
+          0
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid threshold"));
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1],
+          3
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid threshold"));
+});
+
+test("Auth - should not allow transferring operatorship to fewer operators than the minimum", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  // A single operator is enough by default
+  t.is(await contract.view("min_operators", {}), 1);
+
+  const minOperatorsContract = await root.createSubAccount("axelar_min_operators");
+
+  await minOperatorsContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  // The seeded operators are held to the minimum as well
+  const error = await t.throwsAsync(
+    root.call(minOperatorsContract, "new", {
+      recent_operators: [
+        await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(wallets.slice(0, 3)),
+          [1, 1, 1],
+          2
+        ),
+      ],
+      config: {
+        min_operators: 4,
+      },
+    })
+  );
+
+  t.true(error?.message.includes("Too few operators"));
+
+  await root.call(minOperatorsContract, "new", {
+    recent_operators: [],
+    config: {
+      min_operators: 4,
+    },
+  });
+
+  t.is(await minOperatorsContract.view("min_operators", {}), 4);
+
+  const transferError = await t.throwsAsync(
+    root.call(
+      minOperatorsContract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(wallets.slice(0, 3)),
+          [1, 1, 1],
+          2
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(transferError?.message); // uncomment to see the error message
+
+  t.not(transferError, undefined);
+  t.true(transferError?.message.includes("Too few operators"));
+
+  const transferResult: TransferResult = await root.call(
+    minOperatorsContract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(wallets.slice(0, 4)),
+        [1, 1, 1, 1],
+        3
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, 1);
+});
+
+test("Auth - should not allow transferring operatorship with invalid number of weights", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1],
+          0
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid weights"));
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1, 1],
+          3
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid weights"));
+});
+
+test("Auth - should not allow transferring operatorship with weights exceeding u32", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const U32_OVERFLOW = 4294967296;
+
+  const newOperators = [
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+    "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+  ];
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [U32_OVERFLOW, 1],
+          1
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid weights"));
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          [1, 1],
+          U32_OVERFLOW + 1
+        ),
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid threshold"));
+});
+
+test("Auth - should expose correct hashes and epoch", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const operatorsHistory = [...previousOperators, operators];
+
+  await Promise.all(
+    operatorsHistory.map(async (operators, i) => {
+      const payload = await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold
+      );
+
+      const hash = ethers.utils.keccak256(payload);
+
+      const hashForEpoch = await contract.view("hash_for_epoch", {
+        epoch: i + 1,
+      });
+
+      t.is(hashForEpoch, hash);
+
+      const epochForHash = await contract.view("epoch_for_hash", { hash });
+
+      t.is(epochForHash, i + 1);
+    })
+  );
+});
+
+test("Auth - should expose the current epoch", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const currentEpoch = await contract.view("current_epoch", {});
+
+  t.is(currentEpoch, previousOperators.length + 1);
+});
+
+test("Auth - should expose the operators of the current epoch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  t.deepEqual(await contract.view("current_operators", {}), {
+    operators: Utils.getAddresses(operators).map((address) =>
+      address.toLowerCase()
+    ),
+    weights: operators.map(() => "1"),
+    threshold: threshold.toString(),
+  });
+
+  const newOperators = sortBy(wallets.slice(0, 3), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(newOperators),
+        [1, 2, 3],
+        Utils.getFractionalThreshold(5000)
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  // The threshold is the absolute threshold the operators are registered with
+  t.deepEqual(await contract.view("current_operators", {}), {
+    operators: Utils.getAddresses(newOperators).map((address) =>
+      address.toLowerCase()
+    ),
+    weights: ["1", "2", "3"],
+    threshold: "3",
+  });
+});
+
+test("Auth - should count the highest weight signers needed to reach the threshold", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  // The seeded operators all have a weight of 1
+  t.is(await contract.view("min_signers_for_threshold", {}), threshold);
+
+  const cases = [
+    { weights: [100, 1, 1, 1], threshold: 100, minSigners: 1 },
+    { weights: [10, 20, 30, 40], threshold: 67, minSigners: 2 },
+    { weights: [1, 1, 1, 97], threshold: 98, minSigners: 2 },
+    { weights: [5, 5, 5, 5, 80], threshold: 90, minSigners: 3 },
+    { weights: [1, 1, 1, 1, 1, 1], threshold: 6, minSigners: 6 },
+  ];
+
+  for (const [index, { weights, threshold, minSigners }] of cases.entries()) {
+    const newOperators = weights.map(
+      (_, i) =>
+        new ethers.Wallet(ethers.utils.id(`skewed-operator-${index}-${i}`))
+          .address
+    );
+
+    await root.call(
+      contract,
+      "transfer_operatorship",
+      {
+        params: await Utils.getTransferWeightedOperatorshipCommand(
+          newOperators,
+          weights,
+          threshold
+        ),
+      },
+      { attachedDeposit: "0" }
+    );
+
+    t.is(await contract.view("min_signers_for_threshold", {}), minSigners);
+  }
+
+  const emptyContract = await root.createSubAccount("axelar_min_signers");
+
+  await emptyContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  await root.call(emptyContract, "new", { recent_operators: [] });
+
+  t.is(await emptyContract.view("min_signers_for_threshold", {}), null);
+});
+
+test("Auth - should report the health of the auth state", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  t.deepEqual(await contract.view("health", {}), {
+    current_epoch: currentEpoch,
+    old_key_retention: OLD_KEY_RETENTION,
+    has_current_operators: true,
+  });
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(wallets.slice(0, 2)),
+        [1, 1],
+        2
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.deepEqual(await contract.view("health", {}), {
+    current_epoch: currentEpoch + 1,
+    old_key_retention: OLD_KEY_RETENTION,
+    has_current_operators: true,
+  });
+
+  const emptyContract = await root.createSubAccount("axelar_health");
+
+  await emptyContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  await root.call(emptyContract, "new", { recent_operators: [] });
+
+  t.deepEqual(await emptyContract.view("health", {}), {
+    current_epoch: 0,
+    old_key_retention: OLD_KEY_RETENTION,
+    has_current_operators: false,
+  });
+});
+
+test("Auth - should return null hash for an unknown epoch", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const hashForEpoch = await contract.view("hash_for_epoch", {
+    epoch: previousOperators.length + 2,
+  });
+
+  t.is(hashForEpoch, null);
+});
+
+test("Auth - should return null epoch for an unknown hash", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const epochForHash = await contract.view("epoch_for_hash", {
+    hash: ethers.utils.keccak256("0x123abc123abc"),
+  });
+
+  t.is(epochForHash, null);
+});
+
+test("Auth - reject the proof from operators older than a custom key retention", async (t) => {
+  const { root } = t.context.accounts;
+
+  const oldKeyRetention = 2;
+
+  const retentionContract = await root.createSubAccount("axelar_retention");
+
+  await retentionContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const operatorSets = previousOperators.slice(0, 3);
+
+  await root.call(retentionContract, "new", {
+    recent_operators: await Promise.all(
+      operatorSets.map((operators) =>
+        Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(operators),
+          operators.map(() => 1),
+          threshold
+        )
+      )
+    ),
+    config: {
+      old_key_retention: oldKeyRetention,
+    },
+  });
+
+  t.is(await retentionContract.view("old_key_retention", {}), oldKeyRetention);
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const getProof = (operators: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    );
+
+  const error = await t.throwsAsync(
+    retentionContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[0]),
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid epoch"));
+
+  t.is(
+    await retentionContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[1]),
+    }),
+    false
+  );
+
+  t.is(
+    await retentionContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[2]),
+    }),
+    true
+  );
+});
+
+test("Auth - reject a key retention of zero, a retention of one keeps the current operators only", async (t) => {
+  const { root } = t.context.accounts;
+
+  const retentionContract = await root.createSubAccount("axelar_retention");
+
+  await retentionContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const operatorSets = previousOperators.slice(0, 2);
+
+  const recentOperators = await Promise.all(
+    operatorSets.map((operators) =>
+      Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold
+      )
+    )
+  );
+
+  const error = await t.throwsAsync(
+    root.call(retentionContract, "new", {
+      recent_operators: recentOperators,
+      config: {
+        old_key_retention: 0,
+      },
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid key retention"));
+
+  await root.call(retentionContract, "new", {
+    recent_operators: recentOperators,
+    config: {
+      old_key_retention: 1,
+    },
+  });
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const getProof = (operators: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    );
+
+  t.true(
+    await retentionContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[1]),
+    })
+  );
+
+  const expiredError = await t.throwsAsync(
+    retentionContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[0]),
+    })
+  );
+
+  t.not(expiredError, undefined);
+  t.true(expiredError?.message.includes("Invalid epoch"));
+});
+
+test("Auth - keep the retained operators valid across a full rotation", async (t) => {
+  const { root } = t.context.accounts;
+
+  const rotationContract = await root.createSubAccount("axelar_rotation");
+
+  await rotationContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  // The initial operators followed by OLD_KEY_RETENTION + 1 rotations
+  const operatorSets = [...previousOperators, operators].slice(
+    0,
+    OLD_KEY_RETENTION + 2
+  );
+
+  const getParams = (operators: SignerWithAddress[]) =>
+    Utils.getTransferWeightedOperatorshipCommand(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold
+    );
+
+  await root.call(rotationContract, "new", {
+    recent_operators: [await getParams(operatorSets[0])],
+  });
+
+  for (const operators of operatorSets.slice(1)) {
+    await root.call(
+      rotationContract,
+      "transfer_operatorship",
+      { params: await getParams(operators) },
+      { attachedDeposit: "0" }
+    );
+  }
+
+  t.is(await rotationContract.view("current_epoch", {}), OLD_KEY_RETENTION + 2);
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const getProof = (operators: SignerWithAddress[]) =>
+    Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    );
+
+  // The first two epochs are out of the key retention window
+  await Promise.all(
+    operatorSets.slice(0, 2).map(async (operators) => {
+      const error = await t.throwsAsync(
+        rotationContract.view("validate_proof", {
+          message_hash: message,
+          proof: await getProof(operators),
+        })
+      );
+
+      // t.log(error?.message); // uncomment to see the error message
+
+      t.not(error, undefined);
+      t.true(error?.message.includes("Invalid epoch"));
+    })
+  );
+
+  // The oldest retained operators are still valid, but not current
+  t.is(
+    await rotationContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[2]),
+    }),
+    false
+  );
+
+  t.is(
+    await rotationContract.view("validate_proof", {
+      message_hash: message,
+      proof: await getProof(operatorSets[operatorSets.length - 1]),
+    }),
+    true
+  );
+});
+
+test("Auth - seed the operators with the test utils", async (t) => {
+  const { root, john } = t.context.accounts;
+
+  const testUtilsContract = await root.createSubAccount("axelar_test_utils");
+
+  await testUtilsContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near_test_utils.wasm")
+  );
+
+  await root.call(testUtilsContract, "new", { recent_operators: [] });
+
+  const epoch = 42;
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(operators),
+    operators.map(() => 1),
+    threshold
+  );
+  const hash = ethers.utils.keccak256(params);
+
+  // Any account can seed the operators, there is no owner check
+  await john.call(testUtilsContract, "test_set_operators", { epoch, hash });
+
+  t.is(await testUtilsContract.view("current_epoch", {}), epoch);
+  t.is(await testUtilsContract.view("hash_for_epoch", { epoch }), hash);
+  t.is(await testUtilsContract.view("epoch_for_hash", { hash }), epoch);
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.is(
+    await testUtilsContract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    }),
+    true
+  );
+});
+
+test("Auth - stop recovering the signers once the threshold is reached", async (t) => {
+  const { root } = t.context.accounts;
+
+  const testUtilsContract = await root.createSubAccount("axelar_test_utils");
+
+  await testUtilsContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near_test_utils.wasm")
+  );
+
+  const signers = sortBy(wallets.slice(0, 10), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  await root.call(testUtilsContract, "new", {
+    recent_operators: [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(signers),
+        signers.map(() => 1),
+        2
+      ),
+    ],
+  });
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  // All 10 operators sign, but the first 2 signatures reach the threshold
+  const recoveries = await testUtilsContract.view(
+    "test_validate_proof_recoveries",
+    {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        signers,
+        signers.map(() => 1),
+        2,
+        signers
+      ),
+    }
+  );
+
+  t.deepEqual(recoveries, [true, 2]);
+});
+
+test("Auth - log the recovered signers only with verbose logs", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const verboseContract = await root.createSubAccount("axelar_verbose_logs");
+
+  await verboseContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near_test_utils.wasm")
+  );
+
+  await root.call(verboseContract, "new", {
+    recent_operators: [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold
+      ),
+    ],
+  });
+
+  const data = "0x123abc123abc";
+
+  const args = {
+    message_hash: ethers.utils.hashMessage(
+      ethers.utils.arrayify(ethers.utils.keccak256(data))
+    ),
+    proof: await Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    ),
+  };
+
+  const { logs } = await verboseContract.viewRaw("validate_proof", args);
+
+  t.deepEqual(
+    logs.map((log) => JSON.parse(log)),
+    operators.slice(0, threshold).map(({ address }, index) => ({
+      signer: address.toLowerCase(),
+      operator_index: index,
+    }))
+  );
+
+  t.deepEqual((await contract.viewRaw("validate_proof", args)).logs, []);
+});
+
+test("Auth - reject the proof from operators older than the maximum proof age", async (t) => {
+  const { worker } = t.context;
+  const { root } = t.context.accounts;
+
+  const maxProofAge = 60 * 1e9;
+
+  const ageContract = await root.createSubAccount("axelar_age");
+
+  await ageContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  await root.call(ageContract, "new", {
+    recent_operators: [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold
+      ),
+    ],
+    config: {
+      max_proof_age_ns: maxProofAge,
+    },
+  });
+
+  t.is(await ageContract.view("max_proof_age", {}), maxProofAge);
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const args = {
+    message_hash: message,
+    proof: await Utils.getWeightedSignaturesProof(
+      data,
+      operators,
+      operators.map(() => 1),
+      threshold,
+      operators.slice(0, threshold)
+    ),
+  };
+
+  t.true(await ageContract.view("validate_proof", args));
+
+  // Move the block timestamp past the maximum proof age
+  await worker.provider.fastForward(1000);
+
+  const error = await t.throwsAsync(ageContract.view("validate_proof", args));
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Expired proof"));
+
+  t.is(await ageContract.view("can_validate_proof", args), "expired_proof");
+});
+
+test("Auth - should check whether operators are the current operators", async (t) => {
+  const { contract } = t.context.accounts;
+
+  const getParams = (operators: SignerWithAddress[]) =>
+    Utils.getTransferWeightedOperatorshipCommand(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold
+    );
+
+  t.is(
+    await contract.view("is_current_operators", {
+      params: await getParams(operators),
+    }),
+    true
+  );
+
+  t.is(
+    await contract.view("is_current_operators", {
+      params: await getParams(previousOperators[previousOperators.length - 1]),
+    }),
+    false
+  );
+
+  t.is(
+    await contract.view("is_current_operators", { params: "0x123abc" }),
+    false
+  );
+});
+
+test("Auth - should initialize without operators as an empty-state contract", async (t) => {
+  const { root } = t.context.accounts;
+
+  const emptyContract = await root.createSubAccount("axelar_empty");
+
+  await emptyContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const tx = await root.callRaw(emptyContract, "new", { recent_operators: [] });
+
+  t.false(tx.failed);
+  t.true(
+    tx.logs.includes(
+      "Warning: no operators were seeded, proofs can not be validated"
+    )
+  );
+  t.is(await emptyContract.view("current_epoch", {}), 0);
+  t.is(await emptyContract.view("current_operators", {}), null);
+
+  const data = "0x123abc123abc";
+
+  const error = await t.throwsAsync(
+    emptyContract.view("validate_proof", {
+      message_hash: ethers.utils.hashMessage(
+        ethers.utils.arrayify(ethers.utils.keccak256(data))
+      ),
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Unknown operators"));
+});
+
+test("Auth - should validate the seeded operators like an operatorship transfer", async (t) => {
+  const { root } = t.context.accounts;
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(operators),
+    operators.map(() => 1),
+    threshold
+  );
+
+  const invalidSets = [
+    { params: "not hex", message: "Malformed operators params" },
+    {
+      params: ethers.utils.hexDataSlice(params, 0, 64),
+      message: "Malformed operators params",
+    },
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        operators.length + 1
+      ),
+      message: "Invalid threshold",
+    },
+    { params, message: "Duplicate operators" },
+  ];
+
+  for (const [i, { params: invalidParams, message }] of invalidSets.entries()) {
+    const seededContract = await root.createSubAccount(`axelar_seeded_${i}`);
+
+    await seededContract.deploy(
+      path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+    );
+
+    // The last set is seeded twice
+    const recentOperators =
+      message === "Duplicate operators" ? [params, invalidParams] : [invalidParams];
+
+    const error = await t.throwsAsync(
+      root.call(seededContract, "new", { recent_operators: recentOperators })
+    );
+
+    // t.log(error?.message); // uncomment to see the error message
+
+    t.not(error, undefined);
+    t.true(error?.message.includes(message));
+  }
+});
+
+test("Auth - should assign epochs after a custom start epoch", async (t) => {
+  const { root } = t.context.accounts;
+
+  const startEpoch = 100;
+
+  const migratedContract = await root.createSubAccount("axelar_migrated");
+
+  await migratedContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const recentOperators = await Promise.all(
+    previousOperators
+      .slice(0, 3)
+      .map((operators) =>
+        Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(operators),
+          operators.map(() => 1),
+          threshold
+        )
+      )
+  );
+
+  await root.call(migratedContract, "new", {
+    recent_operators: recentOperators,
+    config: {
+      start_epoch: startEpoch,
+    },
+  });
+
+  t.is(await migratedContract.view("current_epoch", {}), startEpoch + 3);
+
+  for (const [i, params] of recentOperators.entries()) {
+    const epochForHash = await migratedContract.view("epoch_for_hash", {
+      hash: ethers.utils.keccak256(params),
+    });
+
+    t.is(epochForHash, startEpoch + i + 1);
+  }
+});
+
+test("Auth - should paginate through the operators history", async (t) => {
+  const { root } = t.context.accounts;
+
+  const historyContract = await root.createSubAccount("axelar_history");
+
+  await historyContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const recentOperators = await Promise.all(
+    previousOperators
+      .slice(0, 5)
+      .map((operators) =>
+        Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(operators),
+          operators.map(() => 1),
+          threshold
+        )
+      )
+  );
+
+  await root.call(historyContract, "new", {
+    recent_operators: recentOperators,
+  });
+
+  const firstPage: [number, string][] = await historyContract.view(
+    "operator_history",
+    { from_epoch: 1, limit: 3 }
+  );
+
+  const secondPage: [number, string][] = await historyContract.view(
+    "operator_history",
+    { from_epoch: 4, limit: 3 }
+  );
+
+  t.is(firstPage.length, 3);
+  t.is(secondPage.length, 2);
+
+  t.deepEqual(
+    [...firstPage, ...secondPage],
+    recentOperators.map((params, i) => [i + 1, ethers.utils.keccak256(params)])
+  );
+});
+
+test("Auth - should prune the epochs out of the key retention window", async (t) => {
+  const { root } = t.context.accounts;
+
+  const oldKeyRetention = 2;
+
+  const pruneContract = await root.createSubAccount("axelar_prune");
+
+  await pruneContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const recentOperators = await Promise.all(
+    previousOperators
+      .slice(0, 5)
+      .map((operators) =>
+        Utils.getTransferWeightedOperatorshipCommand(
+          Utils.getAddresses(operators),
+          operators.map(() => 1),
+          threshold
+        )
+      )
+  );
+
+  await root.call(pruneContract, "new", {
+    recent_operators: recentOperators,
+    config: {
+      old_key_retention: oldKeyRetention,
+    },
+  });
+
+  const { storage_usage: initialStorageUsage } =
+    await pruneContract.accountView();
+
+  const pruned = await root.call(
+    pruneContract,
+    "prune_expired",
+    { up_to_epoch: 10 },
+    { attachedDeposit: "0" }
+  );
+
+  // Epochs 4 and 5 are still within the key retention window
+  t.is(pruned, 3);
+
+  for (const [i, params] of recentOperators.entries()) {
+    const epoch = i + 1;
+    const isExpired = epoch <= recentOperators.length - oldKeyRetention;
+
+    t.is(
+      await pruneContract.view("hash_for_epoch", { epoch }),
+      isExpired ? null : ethers.utils.keccak256(params)
+    );
+    t.is(
+      await pruneContract.view("epoch_for_hash", {
+        hash: ethers.utils.keccak256(params),
+      }),
+      isExpired ? null : epoch
+    );
+  }
+
+  const { storage_usage: storageUsage } = await pruneContract.accountView();
+
+  t.true(storageUsage < initialStorageUsage);
+});
+
+test("Auth - should resume pruning after the last pruned epoch", async (t) => {
+  const { root } = t.context.accounts;
+
+  const oldKeyRetention = 2;
+
+  const pruneContract = await root.createSubAccount("axelar_prune");
+
+  await pruneContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  const getParams = (operators: SignerWithAddress[]) =>
+    Utils.getTransferWeightedOperatorshipCommand(
+      Utils.getAddresses(operators),
+      operators.map(() => 1),
+      threshold
+    );
+
+  await root.call(pruneContract, "new", {
+    recent_operators: await Promise.all(
+      previousOperators.slice(0, 5).map(getParams)
+    ),
+    config: {
+      old_key_retention: oldKeyRetention,
+    },
+  });
+
+  const prune = (upToEpoch: number) =>
+    root.call(
+      pruneContract,
+      "prune_expired",
+      { up_to_epoch: upToEpoch },
+      { attachedDeposit: "0" }
+    );
+
+  t.is(await prune(1), 1);
+  t.is(await prune(10), 2);
+  t.is(await prune(10), 0);
+
+  // The epochs 4 and 5 leave the key retention window
+  for (const operators of previousOperators.slice(5, 7)) {
+    await root.call(
+      pruneContract,
+      "transfer_operatorship",
+      { params: await getParams(operators) },
+      { attachedDeposit: "0" }
+    );
+  }
+
+  t.is(await prune(10), 2);
+  t.is(await pruneContract.view("hash_for_epoch", { epoch: 5 }), null);
+  t.not(await pruneContract.view("hash_for_epoch", { epoch: 6 }), null);
+});
+
+test("Auth - should not allow a non-owner to prune epochs", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const error = await t.throwsAsync(
+    john.call(contract, "prune_expired", { up_to_epoch: 1 })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+});
+
+test("Auth - should revoke the operators of a retained epoch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+  const revokedEpoch = currentEpoch - 1;
+  const revokedOperators = previousOperators[previousOperators.length - 1];
+
+  const proof = await Utils.getWeightedSignaturesProof(
+    data,
+    revokedOperators,
+    revokedOperators.map(() => 1),
+    threshold,
+    revokedOperators.slice(0, threshold)
+  );
+
+  t.false(
+    await contract.view("validate_proof", { message_hash: message, proof })
+  );
+
+  const hash = await contract.view("hash_for_epoch", { epoch: revokedEpoch });
+
+  const tx = await root.callRaw(
+    contract,
+    "revoke_epoch",
+    { epoch: revokedEpoch },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "key_set_revoked_event");
+  t.deepEqual(events[0].data, { epoch: revokedEpoch, operators_hash: hash });
+
+  t.is(await contract.view("hash_for_epoch", { epoch: revokedEpoch }), null);
+  t.is(await contract.view("epoch_for_hash", { hash }), null);
+
+  const error = await t.throwsAsync(
+    contract.view("validate_proof", { message_hash: message, proof })
+  );
+
+  t.true(error?.message.includes("Unknown operators"));
+
+  // The other epochs are not affected
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+  t.not(
+    await contract.view("hash_for_epoch", { epoch: revokedEpoch - 1 }),
+    null
+  );
+});
+
+test("Auth - should not revoke the current or an unknown epoch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "revoke_epoch",
+      { epoch: currentEpoch },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Can not revoke the current epoch"));
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "revoke_epoch",
+      { epoch: currentEpoch + 1 },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Unknown epoch"));
+
+  t.not(await contract.view("hash_for_epoch", { epoch: currentEpoch }), null);
+});
+
+test("Auth - should not allow a non-owner to revoke an epoch", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const error = await t.throwsAsync(
+    john.call(contract, "revoke_epoch", { epoch: currentEpoch - 1 })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+});
+
+test("Auth - should force the current epoch while paused", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+  const forcedEpoch = currentEpoch + 5;
+
+  const recoveryOperators = sortBy(
+    [0, 1, 2].map(
+      (i) => new ethers.Wallet(ethers.utils.id(`recovery-operator-${i}`))
+    ),
+    (wallet) => wallet.address.toLowerCase()
+  ) as unknown as SignerWithAddress[];
+
+  const hash = ethers.utils.keccak256(
+    await Utils.getTransferWeightedOperatorshipCommand(
+      Utils.getAddresses(recoveryOperators),
+      [1, 1, 1],
+      2
+    )
+  );
+
+  await root.call(contract, "pause", {}, { attachedDeposit: "0" });
+
+  const tx = await root.callRaw(
+    contract,
+    "force_set_epoch",
+    { epoch: forcedEpoch, hash },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.logs
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "emergency_state_set_event");
+  t.deepEqual(events[0].data, {
+    previous_epoch: currentEpoch,
+    epoch: forcedEpoch,
+    operators_hash: hash,
+  });
+
+  await root.call(contract, "unpause", {}, { attachedDeposit: "0" });
+
+  t.is(await contract.view("current_epoch", {}), forcedEpoch);
+  t.is(await contract.view("hash_for_epoch", { epoch: forcedEpoch }), hash);
+  t.is(await contract.view("epoch_for_hash", { hash }), forcedEpoch);
+  t.is(await contract.view("current_operators", {}), null);
+  t.deepEqual(await contract.view("health", {}), {
+    current_epoch: forcedEpoch,
+    old_key_retention: OLD_KEY_RETENTION,
+    has_current_operators: true,
+  });
+
+  t.true(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        recoveryOperators,
+        [1, 1, 1],
+        2,
+        recoveryOperators.slice(0, 2)
+      ),
+    })
+  );
+
+  // The operators of the previous current epoch are still within the key retention window
+  t.false(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    })
+  );
+});
+
+test("Auth - should drop the epochs above a lower forced epoch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+  const forcedEpoch = currentEpoch - 2;
+
+  const currentHash: string = await contract.view("hash_for_epoch", {
+    epoch: currentEpoch,
+  });
+
+  await root.call(contract, "pause", {}, { attachedDeposit: "0" });
+
+  await root.call(
+    contract,
+    "force_set_epoch",
+    { epoch: forcedEpoch, hash: ethers.utils.id("recovery-operators-hash") },
+    { attachedDeposit: "0" }
+  );
+
+  await root.call(contract, "unpause", {}, { attachedDeposit: "0" });
+
+  t.is(await contract.view("current_epoch", {}), forcedEpoch);
+
+  for (const epoch of [currentEpoch - 1, currentEpoch]) {
+    t.is(await contract.view("hash_for_epoch", { epoch }), null);
+  }
+
+  t.is(await contract.view("epoch_for_hash", { hash: currentHash }), null);
+
+  // The proof of the operators of the dropped epoch is rejected without panicking on the epoch
+  const error = await t.throwsAsync(
+    contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    })
+  );
+
+  t.true(error?.message.includes("Unknown operators"));
+
+  // The next transfer registers its operators consistently in both maps
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(operators),
+    operators.map(() => 1),
+    threshold
+  );
+
+  const transferResult: TransferResult = await root.call(
+    contract,
+    "transfer_operatorship",
+    { params },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(transferResult.epoch, forcedEpoch + 1);
+  t.is(
+    await contract.view("hash_for_epoch", { epoch: forcedEpoch + 1 }),
+    currentHash
+  );
+  t.is(
+    await contract.view("epoch_for_hash", { hash: currentHash }),
+    forcedEpoch + 1
+  );
+});
+
+test("Auth - should only force the current epoch as the owner of a paused contract", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const currentHash: string = await contract.view("hash_for_epoch", {
+    epoch: currentEpoch,
+  });
+
+  const hash = ethers.utils.id("recovery-operators-hash");
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "force_set_epoch",
+      { epoch: currentEpoch + 1, hash },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Contract is not paused"));
+
+  await root.call(contract, "pause", {}, { attachedDeposit: "0" });
+
+  error = await t.throwsAsync(
+    john.call(contract, "force_set_epoch", { epoch: currentEpoch + 1, hash })
+  );
+
+  t.true(error?.message.includes("Owner only"));
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "force_set_epoch",
+      { epoch: 0, hash },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Invalid epoch"));
+
+  // A hash can not be registered for two epochs
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "force_set_epoch",
+      { epoch: currentEpoch + 1, hash: currentHash },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Duplicate operators"));
+
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+  t.is(
+    await contract.view("hash_for_epoch", { epoch: currentEpoch }),
+    currentHash
+  );
+});
+
+test("Auth - should only execute a time-locked operatorship transfer after the delay", async (t) => {
+  const { worker } = t.context;
+  const { contract, root } = t.context.accounts;
+
+  const timelockDuration = 60 * 1e9;
+
+  await root.call(
+    contract,
+    "set_timelock_duration",
+    { timelock_duration_ns: timelockDuration },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(await contract.view("timelock_duration", {}), timelockDuration);
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(wallets.slice(0, 2)),
+    [1, 1],
+    2
+  );
+  const operation = { transfer_operatorship: { params } };
+
+  let error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation is time-locked"));
+
+  const readyAt: number = await root.call(
+    contract,
+    "schedule_operation",
+    { operation },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(await contract.view("operation_ready_at", { operation }), readyAt);
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "execute_operation",
+      { operation },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation is not ready"));
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+
+  // Move the block timestamp past the time-lock
+  await worker.provider.fastForward(1000);
+
+  const tx = await root.callRaw(
+    contract,
+    "execute_operation",
+    { operation },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.logs
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.deepEqual(
+    events.map(({ event }) => event),
+    ["operatorship_transferred_event", "operation_executed_event"]
+  );
+
+  // The operation returns the same result as the direct transfer
+  const transferResult: TransferResult = tx.parseResult();
+
+  t.is(transferResult.epoch, currentEpoch + 1);
+  t.is(transferResult.operators_hash, ethers.utils.keccak256(params));
+  t.is(await contract.view("current_epoch", {}), currentEpoch + 1);
+  t.is(await contract.view("operation_ready_at", { operation }), null);
+
+  // An executed operation has to be scheduled again
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "execute_operation",
+      { operation },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation not scheduled"));
+});
+
+test("Auth - should time-lock the auth module and the proof limits", async (t) => {
+  const { worker } = t.context;
+  const { contract, root, john } = t.context.accounts;
+
+  await root.call(
+    contract,
+    "set_timelock_duration",
+    { timelock_duration_ns: 60 * 1e9 },
+    { attachedDeposit: "0" }
+  );
+
+  const calls: [string, object, object][] = [
+    [
+      "set_auth_module",
+      { auth_module: john.accountId },
+      { set_auth_module: { auth_module: john.accountId } },
+    ],
+    [
+      "set_max_proof_age",
+      { max_proof_age_ns: 0 },
+      { set_max_proof_age: { max_proof_age_ns: 0 } },
+    ],
+    [
+      "set_max_signatures",
+      { max_signatures: 0 },
+      { set_max_signatures: { max_signatures: 0 } },
+    ],
+  ];
+
+  for (const [method, args, operation] of calls) {
+    const error = await t.throwsAsync(
+      root.call(contract, method, args, { attachedDeposit: "0" })
+    );
+
+    t.true(error?.message.includes("Operation is time-locked"));
+
+    await root.call(
+      contract,
+      "schedule_operation",
+      { operation },
+      { attachedDeposit: "0" }
+    );
+  }
+
+  t.is(await contract.view("auth_module", {}), contract.accountId);
+
+  // Move the block timestamp past the time-lock
+  await worker.provider.fastForward(1000);
+
+  for (const [, , operation] of calls) {
+    t.is(
+      await root.call(
+        contract,
+        "execute_operation",
+        { operation },
+        { attachedDeposit: "0" }
+      ),
+      null
+    );
+  }
+
+  t.is(await contract.view("auth_module", {}), john.accountId);
+});
+
+test("Auth - should cancel a scheduled operation", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  await root.call(
+    contract,
+    "set_timelock_duration",
+    { timelock_duration_ns: 60 * 1e9 },
+    { attachedDeposit: "0" }
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+  const operation = { revoke_epoch: { epoch: currentEpoch - 1 } };
+
+  let error = await t.throwsAsync(
+    john.call(contract, "schedule_operation", { operation })
+  );
+
+  t.true(error?.message.includes("Owner only"));
+
+  await root.call(
+    contract,
+    "schedule_operation",
+    { operation },
+    { attachedDeposit: "0" }
+  );
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "schedule_operation",
+      { operation },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation already scheduled"));
+
+  await root.call(
+    contract,
+    "cancel_operation",
+    { operation },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(await contract.view("operation_ready_at", { operation }), null);
+
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "execute_operation",
+      { operation },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation not scheduled"));
+
+  // The time-lock itself can only be removed through a scheduled operation
+  error = await t.throwsAsync(
+    root.call(
+      contract,
+      "set_timelock_duration",
+      { timelock_duration_ns: null },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Operation is time-locked"));
+});
+
+test("Auth - should migrate the state from the 1.0.0 layout", async (t) => {
+  const { root, contract } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const hash = await contract.view("hash_for_epoch", { epoch: currentEpoch });
+
+  // Simulate the state written by the 1.0.0 contract, the maps keep their prefixes
+  await contract.patchState("STATE", Utils.getLegacyState(currentEpoch));
+
+  await root.call(contract, "migrate", {});
+
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+  t.is(await contract.view("hash_for_epoch", { epoch: currentEpoch }), hash);
+  t.is(await contract.view("old_key_retention", {}), OLD_KEY_RETENTION);
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.true(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    })
+  );
+});
+
+test("Auth - should not allow a non-owner to migrate the state", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  await contract.patchState("STATE", Utils.getLegacyState(currentEpoch));
+
+  const error = await john.call(contract, "migrate", {}).catch((e) => e);
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+});
+
+test("Owner - should reject operatorship transfers while paused", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    [1],
+    1
+  );
+
+  await root.call(contract, "pause", {}, { attachedDeposit: "0" });
+
+  t.is(await contract.view("paus_is_paused", {}), true);
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Contract is paused"));
+
+  const data = "0x123abc123abc";
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.true(
+    await contract.view("validate_proof", {
+      message_hash: message,
+      proof: await Utils.getWeightedSignaturesProof(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    })
+  );
+
+  await root.call(contract, "unpause", {}, { attachedDeposit: "0" });
+
+  t.is(await contract.view("paus_is_paused", {}), false);
+
+  t.true(
+    await root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+});
+
+test("Owner - should not allow a non-owner to pause", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const error = await t.throwsAsync(john.call(contract, "pause", {}));
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+});
+
+test("Owner - should emit the ownership transferred event", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  await root.call(
+    contract,
+    "own_propose_owner",
+    { account_id: john.accountId },
+    { attachedDeposit: "1" }
+  );
+
+  const getEvents = (tx: any) =>
+    tx.result.receipts_outcome
+      .flatMap((receipt: any) => receipt.outcome.logs)
+      .filter((log: string) => log.includes("axelar_near"))
+      .map((log: string) => JSON.parse(log.slice(11)))
+      .filter(({ event }: any) => event === "ownership_transferred_event");
+
+  let events = getEvents(
+    await john.callRaw(contract, "own_accept_owner", {}, { attachedDeposit: "1" })
+  );
+
+  t.is(events.length, 1);
+  t.deepEqual(events[0].data, {
+    previous_owner: root.accountId,
+    new_owner: john.accountId,
+  });
+
+  events = getEvents(
+    await john.callRaw(contract, "own_renounce_owner", {}, { attachedDeposit: "1" })
+  );
+
+  t.is(events.length, 1);
+  t.deepEqual(events[0].data, {
+    previous_owner: john.accountId,
+    new_owner: null,
+  });
+});
+
+test("Owner - should transfer ownership once the proposed owner accepts", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  await root.call(
+    contract,
+    "own_propose_owner",
+    { account_id: john.accountId },
+    { attachedDeposit: "1" }
+  );
+
+  t.is(await contract.view("own_get_owner", {}), root.accountId);
+  t.is(await contract.view("own_get_proposed_owner", {}), john.accountId);
+
+  await john.call(contract, "own_accept_owner", {}, { attachedDeposit: "1" });
+
+  t.is(await contract.view("own_get_owner", {}), john.accountId);
+  t.is(await contract.view("own_get_proposed_owner", {}), null);
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    [1],
+    1
+  );
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Owner only"));
+
+  t.true(
+    await john.call(
+      contract,
+      "transfer_operatorship",
+      { params },
+      { attachedDeposit: "0" }
+    )
+  );
+});
+
+test("Owner - should replace the proposed owner with a later proposal", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const alice = await root.createSubAccount("alice");
+
+  await root.call(
+    contract,
+    "own_propose_owner",
+    { account_id: john.accountId },
+    { attachedDeposit: "1" }
+  );
+
+  await root.call(
+    contract,
+    "own_propose_owner",
+    { account_id: alice.accountId },
+    { attachedDeposit: "1" }
+  );
+
+  t.is(await contract.view("own_get_proposed_owner", {}), alice.accountId);
+
+  const error = await t.throwsAsync(
+    john.call(contract, "own_accept_owner", {}, { attachedDeposit: "1" })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Proposed owner only"));
+
+  await alice.call(contract, "own_accept_owner", {}, { attachedDeposit: "1" });
+
+  t.is(await contract.view("own_get_owner", {}), alice.accountId);
+});
+
+test("Owner - should not allow accepting ownership without a proposal", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const error = await t.throwsAsync(
+    john.call(contract, "own_accept_owner", {}, { attachedDeposit: "1" })
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("No proposed owner"));
+
+  t.is(await contract.view("own_get_owner", {}), root.accountId);
+});
+
+// Gateway Tests
+
+test("Gateway - should fail if chain id mismatches", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID + 1,
+    [Utils.getRandomID()],
+    ["transferOperatorship"],
+    [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        [
+          "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88",
+          "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+        ],
+        [1, 1],
+        2
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "execute",
+      {
+        input,
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined); // Invalid chain id
+});
+
+test("Gateway - should emit an event when the proof is rejected", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const expiredOperators = previousOperators[0];
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [Utils.getRandomID()],
+    ["transferOperatorship"],
+    [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+        [1],
+        1
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    expiredOperators,
+    expiredOperators.map(() => 1),
+    threshold,
+    expiredOperators.slice(0, threshold)
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    { input },
+    { attachedDeposit: "0" }
+  );
+
+  t.true(tx.failed);
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  const message = ethers.utils.hashMessage(
+    ethers.utils.arrayify(ethers.utils.keccak256(data))
+  );
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "proof_validation_failed_event");
+  t.is(events[0].data.message_hash, message);
+  t.is(events[0].data.reason, "expired_epoch");
+});
+
+test("Gateway - should execute the commands of a batch executed concurrently once", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const commandId = Utils.getRandomID();
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        "Polygon",
+        "address0x123",
+        contract.accountId,
+        ethers.utils.keccak256("0x123abc"),
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  // The second batch re-enters before the proof of the first one is validated
+  const txs = await Promise.all(
+    [root, john].map((account) =>
+      account.callRaw(
+        contract,
+        "execute",
+        { input },
+        { attachedDeposit: "0", gas: "300000000000000" }
+      )
+    )
+  );
+
+  t.true(txs.every((tx) => !tx.failed));
+  // The commands the second callback finds executed are skipped
+  const results: boolean[][] = txs.map((tx) => tx.parseResult());
+
+  t.deepEqual(sortBy(results, (result) => result.length), [[], [true]]);
+  t.true(await contract.view("is_command_executed", { command_id: commandId }));
+});
+
+test("Gateway - should keep executing batches after a failed callback", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const getData = async (commandId: string) =>
+    Utils.buildCommandBatch(
+      CHAIN_ID,
+      [commandId],
+      ["approveContractCall"],
+      [
+        await Utils.getApproveContractCall(
+          "Polygon",
+          "address0x123",
+          contract.accountId,
+          ethers.utils.keccak256("0x123abc"),
+          ethers.utils.keccak256("0x123abc123abc"),
+          0
+        ),
+      ]
+    );
+
+  // A junk proof fails the callback
+  const invalidTx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: ethers.utils.defaultAbiCoder.encode(
+        ["bytes", "bytes"],
+        [await getData(Utils.getRandomID()), "0x1234"]
+      ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(invalidTx.failed);
+  t.true(
+    invalidTx.receiptFailureMessages.some((message) =>
+      message.includes("Invalid proof")
+    )
+  );
+
+  const commandId = Utils.getRandomID();
+
+  const validTx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        await getData(commandId),
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.false(validTx.failed);
+  t.deepEqual(validTx.parseResult(), [true]);
+  t.true(await contract.view("is_command_executed", { command_id: commandId }));
+});
+
+test("Gateway - should not allow transferring operatorship to address zero", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const newOperators = [
+    ADDRESS_ZERO,
+    "0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b",
+  ];
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [Utils.getRandomID()],
+    ["transferOperatorship"],
+    [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        newOperators,
+        [1, 1],
+        2
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const error = await t.throwsAsync(
+    root.call(
+      contract,
+      "execute",
+      {
+        input,
+      },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid operators"));
+});
+
+test("Gateway - should approve and validate contract call", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["address"],
+    [owner.address]
+  );
+  const payloadHash = ethers.utils.keccak256(payload);
+  const commandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+  const sourceTxHash = ethers.utils.keccak256("0x123abc123abc");
+  const sourceEventIndex = 17;
+
+  const approveData = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        contract.accountId,
+        payloadHash,
+        sourceTxHash,
+        sourceEventIndex
+      ),
+    ]
+  );
+
+  const approveInput = await Utils.getSignedWeightedExecuteInput(
+    approveData,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const result = await root.call(
+    contract,
+    "execute",
+    {
+      input: approveInput,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.deepEqual(result, [true]);
+
+  const isApprovedBefore = await contract.view("is_contract_call_approved", {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    contract_address: contract.accountId,
+    payload_hash: payloadHash,
+  });
+
+  t.is(isApprovedBefore, true);
+
+  await contract.call(
+    contract,
+    "validate_contract_call",
+    {
+      command_id: commandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      payload_hash: payloadHash,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const isApprovedAfter = await contract.view("is_contract_call_approved", {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    contract_address: contract.accountId,
+    payload_hash: payloadHash,
+  });
+
+  t.is(isApprovedAfter, false);
+});
+
+test("Gateway - should only transfer operatorship with a proof from the current operators", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const recentOperators = previousOperators[previousOperators.length - 1];
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const approveCommandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+
+  const newOperatorsParams = await Utils.getTransferWeightedOperatorshipCommand(
+    ["0x6D4017D4b1DCd36e6EA88b7900e8eC64A1D1315b"],
+    [1],
+    1
+  );
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [approveCommandId, Utils.getRandomID()],
+    ["approveContractCall", "transferOperatorship"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        contract.accountId,
+        payloadHash,
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+      newOperatorsParams,
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    recentOperators,
+    recentOperators.map(() => 1),
+    threshold,
+    recentOperators.slice(0, threshold)
+  );
+
+  const currentEpoch = await contract.view("current_epoch", {});
+
+  const result = await root.call(
+    contract,
+    "execute",
+    { input },
+    { attachedDeposit: "0" }
+  );
+
+  // The previous operators can approve calls, but not rotate the operators
+  t.deepEqual(result, [true]);
+
+  t.is(await contract.view("current_epoch", {}), currentEpoch);
+  t.is(
+    await contract.view("epoch_for_hash", {
+      hash: ethers.utils.keccak256(newOperatorsParams),
+    }),
+    null
+  );
+
+  t.true(
+    await contract.view("is_contract_call_approved", {
+      command_id: approveCommandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      contract_address: contract.accountId,
+      payload_hash: payloadHash,
+    })
+  );
+});
+
+test("Gateway - should only report the exact approved contract call", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const commandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        contract.accountId,
+        payloadHash,
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  await root.call(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const isContractCallApproved = (payloadHash: string) =>
+    contract.view("is_contract_call_approved", {
+      command_id: commandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      contract_address: contract.accountId,
+      payload_hash: payloadHash,
+    });
+
+  t.is(await isContractCallApproved(payloadHash), true);
+  t.is(
+    await isContractCallApproved(ethers.utils.keccak256("0x123abd")),
+    false
+  );
+});
+
+test("Gateway - should consume a contract call approval once", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const commandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        john.accountId,
+        payloadHash,
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  await root.call(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const args = {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    payload_hash: payloadHash,
+  };
+
+  // The approval is keyed by the calling contract
+  t.false(await root.call(contract, "validate_contract_call", args));
+
+  t.true(await john.call(contract, "validate_contract_call", args));
+  t.false(await john.call(contract, "validate_contract_call", args));
+
+  t.false(
+    await contract.view("is_contract_call_approved", {
+      ...args,
+      contract_address: john.accountId,
+    })
+  );
+});
+
+test("Gateway - should skip the commands of a replayed batch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const commandId = Utils.getRandomID();
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        "Polygon",
+        "address0x123",
+        contract.accountId,
+        ethers.utils.keccak256("0x123abc"),
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const getEvents = async () => {
+    const tx = await root.callRaw(
+      contract,
+      "execute",
+      { input },
+      { attachedDeposit: "0" }
+    );
+
+    return tx.result.receipts_outcome
+      .map((receipt) => receipt.outcome.logs.map((log) => log))
+      .flatMap((log) => log)
+      .filter((log) => log.includes("axelar_near"))
+      .map((event) => JSON.parse(event.slice(11)));
+  };
+
+  const events = await getEvents();
+
+  t.true(events.some(({ event }) => event === "executed_event"));
+  t.true(await contract.view("is_command_executed", { command_id: commandId }));
+
+  // The replay is accepted, but the executed command is skipped
+  t.deepEqual(await getEvents(), []);
+  t.deepEqual(
+    await root.call(contract, "execute", { input }, { attachedDeposit: "0" }),
+    []
+  );
+});
+
+test("Gateway - should execute a batch split over several calls once", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const commandIds = [0, 1, 2].map(() => Utils.getRandomID());
+  const payloadHashes = commandIds.map((_, index) =>
+    ethers.utils.keccak256(ethers.utils.hexlify(index + 1))
+  );
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    commandIds,
+    commandIds.map(() => "approveContractCall"),
+    await Promise.all(
+      payloadHashes.map((payloadHash) =>
+        Utils.getApproveContractCall(
+          "Polygon",
+          "address0x123",
+          contract.accountId,
+          payloadHash,
+          ethers.utils.keccak256("0x123abc123abc"),
+          0
+        )
+      )
+    )
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const execute = (command_selector?: number[]) =>
+    root.call(
+      contract,
+      "execute",
+      { input, command_selector },
+      { attachedDeposit: "0", gas: "300000000000000" }
+    );
+
+  const error = await t.throwsAsync(execute([3]));
+
+  // t.log(error?.message); // uncomment to see the error message
+
+  t.not(error, undefined);
+  t.true(error?.message.includes("Invalid command selector"));
+
+  t.deepEqual(await execute([0, 2]), [true, true]);
+  t.false(
+    await contract.view("is_command_executed", { command_id: commandIds[1] })
+  );
+
+  // The commands executed by the first call are skipped
+  t.deepEqual(await execute([1, 2]), [true]);
+  t.deepEqual(await execute(), []);
+
+  for (const [index, commandId] of commandIds.entries()) {
+    t.true(await contract.view("is_command_executed", { command_id: commandId }));
+    t.true(
+      await contract.view("is_contract_call_approved", {
+        command_id: commandId,
+        source_chain: "Polygon",
+        source_address: "address0x123",
+        contract_address: contract.accountId,
+        payload_hash: payloadHashes[index],
+      })
+    );
+  }
+});
+
+test("Gateway - should emit the contract call approved event", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const commandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+  const sourceTxHash = ethers.utils.keccak256("0x123abc123abc");
+  const sourceEventIndex = 17;
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        contract.accountId,
+        payloadHash,
+        sourceTxHash,
+        sourceEventIndex
+      ),
+    ]
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)))
+    .filter(({ event }) => event === "contract_call_approved_event");
+
+  t.is(events.length, 1);
+  t.deepEqual(events[0].data, {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    contract_address: contract.accountId,
+    payload_hash: payloadHash,
+    source_tx_hash: sourceTxHash,
+    source_event_index: sourceEventIndex,
+  });
+});
+
+test("Gateway - should record the epoch of the operators that signed the batch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const commandId = Utils.getRandomID();
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        "Polygon",
+        "address0x123",
+        contract.accountId,
+        ethers.utils.keccak256("0x123abc"),
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  const retainedOperators = previousOperators[previousOperators.length - 1];
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        retainedOperators,
+        retainedOperators.map(() => 1),
+        threshold,
+        retainedOperators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.startsWith("EVENT_JSON:"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const executedEvents = events.filter(
+    ({ event }) => event === "executed_event"
+  );
+
+  t.is(executedEvents.length, 1);
+  // The batch index and the operators epoch were added in the 1.1.0 version of the events
+  t.is(executedEvents[0].version, "1.1.0");
+  t.deepEqual(executedEvents[0].data, {
+    command_id: commandId,
+    batch_index: 0,
+    operators_epoch: currentEpoch - 1,
+  });
+
+  // The proof of the batch is validated with the event of the signing operators
+  const validatedEvents = events.filter(
+    ({ event }) => event === "proof_validated_event"
+  );
+
+  t.is(validatedEvents.length, 1);
+  t.is(validatedEvents[0].data.operators_epoch, currentEpoch - 1);
+  t.false(validatedEvents[0].data.is_current);
+});
+
+test("Gateway - call contract event will emit", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const chain = "Polygon";
+  const destination = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["address", "address"],
+    [wallets[1].address, wallets[2].address]
+  );
+
+  const event: {
+    address: string;
+    destination_chain: string;
+    destination_contract_address: string;
+    payload_hash: string;
+    payload: string;
+  } = await contract.call(
+    contract,
+    "call_contract",
+    {
+      destination_chain: chain,
+      destination_contract_address: destination,
+      payload,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(event.address, contract.accountId);
+  t.is(event.destination_chain, chain);
+  t.is(event.destination_contract_address, destination);
+  t.is(event.payload_hash, ethers.utils.keccak256(payload));
+  t.is(event.payload, payload);
+});
+
+test("Gateway - call contract emits the contract call event in the logs", async (t) => {
+  const { contract, john } = t.context.accounts;
+
+  const chain = "Polygon";
+  const destination = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["address", "address"],
+    [wallets[1].address, wallets[2].address]
+  );
+
+  const tx = await john.callRaw(
+    contract,
+    "call_contract",
+    {
+      destination_chain: chain,
+      destination_contract_address: destination,
+      payload,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].standard, "axelar_near");
+  t.is(events[0].event, "contract_call_event");
+  t.is(events[0].data.address, john.accountId);
+  t.is(events[0].data.destination_chain, chain);
+  t.is(events[0].data.destination_contract_address, destination);
+  t.is(events[0].data.payload_hash, ethers.utils.keccak256(payload));
+  t.is(events[0].data.payload, payload);
+});
+
+// Executable Near Contract tests
+test("Gateway - call executable contract", async (t) => {
+  const { worker, root, contract, executableContract } = t.context.accounts;
+
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["string"],
+    ["Hello from Polygon!"]
+  );
+
+  const payloadHash = ethers.utils.keccak256(payload);
+  const commandId = Utils.getRandomID();
+  const sourceChain = "Polygon";
+  const sourceAddress = "address0x123";
+  const sourceTxHash = ethers.utils.keccak256("0x123abc123abc");
+  const sourceEventIndex = 17;
+
+  const approveData = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        sourceChain,
+        sourceAddress,
+        executableContract.accountId,
+        payloadHash,
+        sourceTxHash,
+        sourceEventIndex
+      ),
+    ]
+  );
+
+  const approveInput = await Utils.getSignedWeightedExecuteInput(
+    approveData,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const result = await root.call(
+    contract,
+    "execute",
+    {
+      input: approveInput,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  t.deepEqual(result, [true]);
+
+  const isApprovedBefore = await contract.view("is_contract_call_approved", {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    contract_address: executableContract.accountId,
+    payload_hash: payloadHash,
+  });
+
+  t.is(isApprovedBefore, true);
+
+  await contract.call(
+    executableContract,
+    "execute",
+    {
+      command_id: commandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      payload,
+    },
+    { attachedDeposit: "0" }
+  );
+
+  const isApprovedAfter = await contract.view("is_contract_call_approved", {
+    command_id: commandId,
+    source_chain: sourceChain,
+    source_address: sourceAddress,
+    contract_address: executableContract.accountId,
+    payload_hash: payloadHash,
+  });
+
+  t.is(isApprovedAfter, false);
+
+  const value = await executableContract.view("get_value", {});
+
+  const contractSourceChain = await executableContract.view(
+    "get_source_chain",
+    {}
+  );
+  const contractSourceAddress = await executableContract.view(
+    "get_source_address",
+    {}
+  );
+
+  t.is(value, "Hello from Polygon!");
+  t.is(contractSourceChain, sourceChain);
+  t.is(contractSourceAddress, sourceAddress);
+});
+
+test("Gateway - should not execute an unapproved call on the executable contract", async (t) => {
+  const { root, executableContract } = t.context.accounts;
+
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["string"],
+    ["Hello from Polygon!"]
+  );
+
+  const tx = await root.callRaw(
+    executableContract,
+    "execute",
+    {
+      command_id: Utils.getRandomID(),
+      source_chain: "Polygon",
+      source_address: "address0x123",
+      payload,
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(tx.failed);
+  t.true(
+    tx.receiptFailureMessages.some((message) =>
+      message.includes("Not approved by gateway")
+    )
+  );
+
+  t.is(await executableContract.view("get_value", {}), null);
+  t.is(await executableContract.view("get_source_chain", {}), null);
+});
+
+test("Gateway - should emit contract called event in a cross contract call", async (t) => {
+  const { worker, root, contract, executableContract } = t.context.accounts;
+
+  const payload = ethers.utils.defaultAbiCoder.encode(
+    ["string"],
+    ["Hello from Near!"]
+  );
+
+  const payloadHash = ethers.utils.keccak256(payload);
+
+  const chain = "ethereum";
+  const destinationAddress = "address0x123";
+  const value = "Hello from Near!";
+
+  const tx = await executableContract.callRaw(
+    executableContract,
+    "set",
+    {
+      chain,
+      destination_address: destinationAddress,
+      value,
+    },
+    {
+      attachedDeposit: "0",
+    }
+  );
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events[0].standard, "axelar_near");
+  t.is(events[0].version, "1.1.0");
+  t.is(events[0].event, "contract_call_event");
+  t.is(events[0].data.destination_chain, chain);
+  t.is(events[0].data.destination_contract_address, destinationAddress);
+  t.is(events[0].data.payload, payload);
+  t.is(events[0].data.payload_hash, payloadHash);
+});
+
+test("Gateway - should deploy a token", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  await setTokenCode(root, contract);
+
+  const name = "An Awesome Axelar Token";
+  const symbol = "AAT";
+  const decimals = 18;
+  const cap = 1e9;
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [Utils.getRandomID()],
+    ["deployToken"],
+    [await Utils.getDeployTokenCommand(name, symbol, decimals, cap, "", 0)]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    { input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.false(tx.failed);
+
+  const tokenAccountId = `aat.${contract.accountId}`;
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  const tokenDeployedEvent = events.find(
+    (event) => event.event === "token_deployed_event"
+  );
+
+  t.is(tokenDeployedEvent.data.symbol, symbol);
+  t.is(tokenDeployedEvent.data.token_address, tokenAccountId);
+
+  const token = root.getAccount(tokenAccountId);
+
+  t.true(await token.exists());
+
+  const metadata: any = await token.view("ft_metadata", {});
+
+  t.is(metadata.name, name);
+  t.is(metadata.symbol, symbol);
+  t.is(metadata.decimals, decimals);
+  t.is(await token.view("cap", {}), cap.toString());
+  t.is(await token.view("ft_total_supply", {}), "0");
+});
+
+test("Gateway - should mint a deployed token", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  await setTokenCode(root, contract);
+
+  const symbol = "AAT";
+  const mintLimit = 1000;
+
+  await executeCommands(
+    root,
+    contract,
+    ["deployToken"],
+    [await Utils.getDeployTokenCommand("Token", symbol, 18, 0, "", mintLimit)]
+  );
+
+  const tx = await executeCommands(
+    root,
+    contract,
+    ["mintToken", "mintToken", "mintToken"],
+    [
+      await Utils.getMintCommand(symbol, john.accountId, mintLimit),
+      await Utils.getMintCommand(symbol, john.accountId, mintLimit + 1),
+      await Utils.getMintCommand("UNKNOWN", john.accountId, 1),
+    ]
+  );
+
+  t.false(tx.failed);
+
+  // Amounts over the mint limit and unknown symbols are skipped
+  t.deepEqual(tx.parseResult(), [true, false, false]);
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("nep141"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "ft_mint");
+  t.is(events[0].data[0].owner_id, john.accountId);
+  t.is(events[0].data[0].amount, mintLimit.toString());
+
+  const token = root.getAccount(`aat.${contract.accountId}`);
+
+  t.is(
+    await token.view("ft_balance_of", { account_id: john.accountId }),
+    mintLimit.toString()
+  );
+});
+
+test("Gateway - should roll back a token deployment that failed", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  // A code whose initialization rejects the token arguments
+  await root.call(
+    contract,
+    "set_token_code",
+    {
+      code: fs
+        .readFileSync(path.join(__dirname, "../../dist/axelar_cgp_near.wasm"))
+        .toString("base64"),
+    },
+    { gas: "300000000000000" }
+  );
+
+  const commandId = Utils.getRandomID();
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["deployToken"],
+    [await Utils.getDeployTokenCommand("Token", "AAT", 18, 0, "", 0)]
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.deepEqual(
+    events.find(({ event }) => event === "command_failed_event")?.data,
+    { command_id: commandId, batch_index: 0 }
+  );
+  t.is(await contract.view("token_address", { symbol: "AAT" }), null);
+  t.false(await contract.view("is_command_executed", { command_id: commandId }));
+  t.false(await root.getAccount(`aat.${contract.accountId}`).exists());
+});
+
+test("Gateway - should roll back a mint that failed", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  // The external token is an account without a token contract
+  await executeCommands(
+    root,
+    contract,
+    ["deployToken"],
+    [
+      await Utils.getDeployTokenCommand(
+        "Token",
+        "AAT",
+        18,
+        0,
+        john.accountId,
+        0
+      ),
+    ]
+  );
+
+  const commandId = Utils.getRandomID();
 
   const data = await Utils.buildCommandBatch(
     CHAIN_ID,
-    [Utils.getRandomID()],
-    ["transferOperatorship"],
-    [
-      await Utils.getTransferWeightedOperatorshipCommand(
-        newOperators,
-        [1, 1],
-        2
+    [commandId],
+    ["mintToken"],
+    [await Utils.getMintCommand("AAT", john.accountId, 1)]
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
       ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.deepEqual(
+    events.find(({ event }) => event === "command_failed_event")?.data,
+    { command_id: commandId, batch_index: 0 }
+  );
+  t.false(await contract.view("is_command_executed", { command_id: commandId }));
+
+  // The token registration of the mint is kept
+  t.is(await contract.view("token_address", { symbol: "AAT" }), john.accountId);
+});
+
+test("Gateway - should burn a deployed token sent to another chain", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  await setTokenCode(root, contract);
+
+  const symbol = "AAT";
+  const destinationChain = "Polygon";
+  const destinationAddress = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
+
+  await executeCommands(
+    root,
+    contract,
+    ["deployToken", "mintToken"],
+    [
+      await Utils.getDeployTokenCommand("Token", symbol, 18, 0, "", 0),
+      await Utils.getMintCommand(symbol, john.accountId, 1000),
     ]
   );
 
-  const input = await Utils.getSignedWeightedExecuteInput(
-    data,
-    operators,
-    operators.map(() => 1),
-    threshold,
-    operators.slice(0, threshold)
+  const tx = await john.callRaw(
+    contract,
+    "send_token",
+    {
+      destination_chain: destinationChain,
+      destination_address: destinationAddress,
+      symbol,
+      amount: "400",
+    },
+    { attachedDeposit: "1", gas: "300000000000000" }
   );
 
-  const error = await t.throwsAsync(
-    root.call(
-      contract,
-      "execute",
-      {
-        input,
-      },
-      { attachedDeposit: "0" }
-    )
+  t.false(tx.failed);
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
+
+  t.is(events.length, 1);
+  t.is(events[0].event, "token_sent_event");
+  t.is(events[0].data.sender, john.accountId);
+  t.is(events[0].data.destination_chain, destinationChain);
+  t.is(events[0].data.destination_address, destinationAddress);
+  t.is(events[0].data.symbol, symbol);
+  t.is(events[0].data.amount, "400");
+
+  const token = root.getAccount(`aat.${contract.accountId}`);
+
+  t.is(
+    await token.view("ft_balance_of", { account_id: john.accountId }),
+    "600"
   );
+  t.is(await token.view("ft_total_supply", {}), "600");
+});
 
-  // t.log(error?.message); // uncomment to see the error message
+test("Gateway - should lock an external token sent to another chain", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const symbol = "EXT";
+  const destinationChain = "Polygon";
+  const destinationAddress = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
+
+  const token = await root.createSubAccount("external_token");
+
+  await token.deploy(TOKEN_WASM);
+
+  await root.call(token, "new", {
+    name: "External Token",
+    symbol,
+    decimals: 6,
+    cap: "0",
+  });
+
+  await root.call(token, "mint", { account_id: john.accountId, amount: "1000" });
+
+  await executeCommands(
+    root,
+    contract,
+    ["deployToken"],
+    [
+      await Utils.getDeployTokenCommand(
+        "External Token",
+        symbol,
+        6,
+        0,
+        token.accountId,
+        0
+      ),
+    ]
+  );
+
+  const tx = await john.callRaw(
+    token,
+    "ft_transfer_call",
+    {
+      receiver_id: contract.accountId,
+      amount: "400",
+      msg: JSON.stringify({
+        destination_chain: destinationChain,
+        destination_address: destinationAddress,
+        symbol,
+      }),
+    },
+    { attachedDeposit: "1", gas: "300000000000000" }
+  );
+
+  t.false(tx.failed);
+
+  const events = tx.result.receipts_outcome
+    .map((receipt) => receipt.outcome.logs.map((log) => log))
+    .flatMap((log) => log)
+    .filter((log) => log.includes("axelar_near"))
+    .map((event) => JSON.parse(event.slice(11)));
 
-  t.not(error, undefined); // Invalid operators
+  t.is(events.length, 1);
+  t.is(events[0].event, "token_sent_event");
+  t.is(events[0].data.sender, john.accountId);
+  t.is(events[0].data.destination_chain, destinationChain);
+  t.is(events[0].data.destination_address, destinationAddress);
+  t.is(events[0].data.symbol, symbol);
+  t.is(events[0].data.amount, "400");
+
+  t.is(
+    await token.view("ft_balance_of", { account_id: john.accountId }),
+    "600"
+  );
+  t.is(
+    await token.view("ft_balance_of", { account_id: contract.accountId }),
+    "400"
+  );
+  t.is(await token.view("ft_total_supply", {}), "1000");
 });
 
-test("Gateway - should approve and validate contract call", async (t) => {
+test("Gateway - should resolve the symbol of a deployed token", async (t) => {
   const { contract, root } = t.context.accounts;
 
-  const payload = ethers.utils.defaultAbiCoder.encode(
-    ["address"],
-    [owner.address]
+  await setTokenCode(root, contract);
+
+  await executeCommands(
+    root,
+    contract,
+    ["deployToken"],
+    [await Utils.getDeployTokenCommand("Token", "AAT", 18, 0, "", 0)]
   );
-  const payloadHash = ethers.utils.keccak256(payload);
-  const commandId = Utils.getRandomID();
+
+  t.is(
+    await contract.view("token_address", { symbol: "AAT" }),
+    `aat.${contract.accountId}`
+  );
+  t.is(await contract.view("token_address", { symbol: "UNKNOWN" }), null);
+});
+
+test("Gateway - should skip unknown commands without reverting the batch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const approveCommandId = Utils.getRandomID();
+  const unknownCommandId = Utils.getRandomID();
   const sourceChain = "Polygon";
   const sourceAddress = "address0x123";
-  const sourceTxHash = ethers.utils.keccak256("0x123abc123abc");
-  const sourceEventIndex = 17;
 
-  const approveData = await Utils.buildCommandBatch(
+  const data = await Utils.buildCommandBatch(
     CHAIN_ID,
-    [commandId],
-    ["approveContractCall"],
+    [unknownCommandId, approveCommandId],
+    ["unknownCommand", "approveContractCall"],
     [
+      "0x1234",
       await Utils.getApproveContractCall(
         sourceChain,
         sourceAddress,
         contract.accountId,
         payloadHash,
-        sourceTxHash,
-        sourceEventIndex
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
       ),
     ]
   );
 
-  const approveInput = await Utils.getSignedWeightedExecuteInput(
-    approveData,
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
     operators,
     operators.map(() => 1),
     threshold,
@@ -742,98 +5964,288 @@ test("Gateway - should approve and validate contract call", async (t) => {
   const result = await root.call(
     contract,
     "execute",
-    {
-      input: approveInput,
-    },
+    { input },
     { attachedDeposit: "0" }
   );
 
   t.deepEqual(result, [true]);
 
-  const isApprovedBefore = await contract.view("is_contract_call_approved", {
-    command_id: commandId,
-    source_chain: sourceChain,
-    source_address: sourceAddress,
-    contract_address: contract.accountId,
-    payload_hash: payloadHash,
-  });
+  t.true(
+    await contract.view("is_contract_call_approved", {
+      command_id: approveCommandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      contract_address: contract.accountId,
+      payload_hash: payloadHash,
+    })
+  );
+  t.true(
+    await contract.view("is_command_executed", {
+      command_id: unknownCommandId,
+    })
+  );
+});
 
-  t.is(isApprovedBefore, true);
+test("Gateway - should emit an event for each command of a batch with its index", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
+  const approveCommandId = Utils.getRandomID();
+  const unknownCommandId = Utils.getRandomID();
+  const mintCommandId = Utils.getRandomID();
+
+  const approveCommand = await Utils.getApproveContractCall(
+    "Polygon",
+    "address0x123",
+    contract.accountId,
+    payloadHash,
+    ethers.utils.keccak256("0x123abc123abc"),
+    0
+  );
 
-  await contract.call(
+  // The approval is repeated with the same command id, and the token to mint is not registered
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [approveCommandId, unknownCommandId, approveCommandId, mintCommandId],
+    ["approveContractCall", "unknownCommand", "approveContractCall", "mintToken"],
+    [
+      approveCommand,
+      "0x1234",
+      approveCommand,
+      await Utils.getMintCommand("UNKNOWN", john.accountId, 1),
+    ]
+  );
+
+  const currentEpoch: number = await contract.view("current_epoch", {});
+
+  const tx = await root.callRaw(
     contract,
-    "validate_contract_call",
+    "execute",
     {
-      command_id: commandId,
-      source_chain: sourceChain,
-      source_address: sourceAddress,
-      payload_hash: payloadHash,
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
     },
     { attachedDeposit: "0" }
   );
 
-  const isApprovedAfter = await contract.view("is_contract_call_approved", {
-    command_id: commandId,
-    source_chain: sourceChain,
-    source_address: sourceAddress,
-    contract_address: contract.accountId,
-    payload_hash: payloadHash,
+  t.deepEqual(tx.parseResult(), [true, false]);
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)))
+    .filter(({ event }) =>
+      [
+        "contract_call_approved_event",
+        "executed_event",
+        "command_skipped_event",
+        "command_failed_event",
+      ].includes(event)
+    );
+
+  t.deepEqual(
+    events.map(({ event }) => event),
+    [
+      "contract_call_approved_event",
+      "executed_event",
+      "command_skipped_event",
+      "command_skipped_event",
+      "command_failed_event",
+    ]
+  );
+  t.deepEqual(
+    events.slice(1).map(({ data }) => data),
+    [
+      {
+        command_id: approveCommandId,
+        batch_index: 0,
+        operators_epoch: currentEpoch,
+      },
+      {
+        command_id: unknownCommandId,
+        batch_index: 1,
+        reason: "unknown_command",
+      },
+      {
+        command_id: approveCommandId,
+        batch_index: 2,
+        reason: "already_executed",
+      },
+      { command_id: mintCommandId, batch_index: 3 },
+    ]
+  );
+
+  // The failed command can be executed again
+  t.false(
+    await contract.view("is_command_executed", { command_id: mintCommandId })
+  );
+});
+
+test("Gateway - should reject a batch signed for another deployment", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const chainId = 5;
+
+  const siblingContract = await root.createSubAccount("axelar_sibling");
+
+  await siblingContract.deploy(
+    path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+  );
+
+  await root.call(siblingContract, "new", {
+    recent_operators: [
+      await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(operators),
+        operators.map(() => 1),
+        threshold
+      ),
+    ],
+    config: {
+      chain_id: chainId,
+    },
   });
 
-  t.is(isApprovedAfter, false);
+  t.is(await siblingContract.view("chain_id", {}), chainId);
+  t.is(await contract.view("chain_id", {}), CHAIN_ID);
+
+  const data = await Utils.buildCommandBatch(
+    chainId,
+    [Utils.getRandomID()],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        "Polygon",
+        "address0x123",
+        contract.accountId,
+        ethers.utils.keccak256("0x123abc"),
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
+  );
+
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    operators,
+    operators.map(() => 1),
+    threshold,
+    operators.slice(0, threshold)
+  );
+
+  // Both deployments share the operators, only the sibling accepts the batch
+  const error = await t.throwsAsync(
+    root.call(contract, "execute", { input }, { attachedDeposit: "0" })
+  );
+
+  t.true(error?.message.includes(`Invalid chain id: ${chainId}`));
+
+  const result = await root.call(
+    siblingContract,
+    "execute",
+    { input },
+    { attachedDeposit: "0" }
+  );
+
+  t.deepEqual(result, [true]);
 });
 
-test("Gateway - call contract event will emit", async (t) => {
+test("Gateway - should not execute a batch signed for another domain", async (t) => {
   const { contract, root } = t.context.accounts;
 
-  const chain = "Polygon";
-  const destination = "0xb7900E8Ec64A1D1315B6D4017d4b1dcd36E6Ea88";
-  const payload = ethers.utils.defaultAbiCoder.encode(
-    ["address", "address"],
-    [wallets[1].address, wallets[2].address]
+  const commandId = Utils.getRandomID();
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["approveContractCall"],
+    [
+      await Utils.getApproveContractCall(
+        "Polygon",
+        "address0x123",
+        contract.accountId,
+        ethers.utils.keccak256("0x123abc"),
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
+      ),
+    ]
   );
 
-  const event: {
-    address: string;
-    destination_chain: string;
-    destination_contract_address: string;
-    payload_hash: string;
-    payload: string;
-  } = await contract.call(
+  const getInput = async (signedData: string) =>
+    ethers.utils.defaultAbiCoder.encode(
+      ["bytes", "bytes"],
+      [
+        data,
+        await Utils.getWeightedSignaturesProof(
+          signedData,
+          operators,
+          operators.map(() => 1),
+          threshold,
+          operators.slice(0, threshold)
+        ),
+      ]
+    );
+
+  // Signed for the operatorship transfers
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    {
+      input: await getInput(
+        Utils.getDomainData(Utils.OPERATORSHIP_TRANSFER_DOMAIN, data)
+      ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(tx.failed);
+  t.true(
+    tx.receiptFailureMessages.some((message) =>
+      message.includes("Invalid proof")
+    )
+  );
+
+  t.false(await contract.view("is_command_executed", { command_id: commandId }));
+
+  const result = await root.call(
     contract,
-    "call_contract",
+    "execute",
     {
-      destination_chain: chain,
-      destination_contract_address: destination,
-      payload,
+      input: await getInput(ethers.utils.hexlify(data)),
     },
     { attachedDeposit: "0" }
   );
 
-  t.is(event.address, contract.accountId);
-  t.is(event.destination_chain, chain);
-  t.is(event.destination_contract_address, destination);
-  t.is(event.payload_hash, ethers.utils.keccak256(payload));
-  t.is(event.payload, payload);
+  t.deepEqual(result, [true]);
 });
 
-// Executable Near Contract tests
-test("Gateway - call executable contract", async (t) => {
-  const { worker, root, contract, executableContract } = t.context.accounts;
+test("Gateway - should validate the proofs with a separate auth module", async (t) => {
+  const { contract, root } = t.context.accounts;
 
-  const payload = ethers.utils.defaultAbiCoder.encode(
-    ["string"],
-    ["Hello from Polygon!"]
-  );
+  const gateway = await root.createSubAccount("axelar_gateway");
 
-  const payloadHash = ethers.utils.keccak256(payload);
+  await gateway.deploy(path.join(__dirname, "../../dist/axelar_cgp_near.wasm"));
+
+  await root.call(gateway, "new", {
+    recent_operators: [],
+    config: {
+      auth_module: contract.accountId,
+    },
+  });
+
+  t.is(await gateway.view("auth_module", {}), contract.accountId);
+  t.is(await contract.view("auth_module", {}), contract.accountId);
+
+  const payloadHash = ethers.utils.keccak256("0x123abc");
   const commandId = Utils.getRandomID();
   const sourceChain = "Polygon";
   const sourceAddress = "address0x123";
-  const sourceTxHash = ethers.utils.keccak256("0x123abc123abc");
-  const sourceEventIndex = 17;
 
-  const approveData = await Utils.buildCommandBatch(
+  const data = await Utils.buildCommandBatch(
     CHAIN_ID,
     [commandId],
     ["approveContractCall"],
@@ -841,119 +6253,452 @@ test("Gateway - call executable contract", async (t) => {
       await Utils.getApproveContractCall(
         sourceChain,
         sourceAddress,
-        executableContract.accountId,
+        gateway.accountId,
         payloadHash,
-        sourceTxHash,
-        sourceEventIndex
+        ethers.utils.keccak256("0x123abc123abc"),
+        0
       ),
     ]
   );
 
-  const approveInput = await Utils.getSignedWeightedExecuteInput(
-    approveData,
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
     operators,
     operators.map(() => 1),
     threshold,
     operators.slice(0, threshold)
   );
 
+  // The operators are only registered in the auth module
   const result = await root.call(
-    contract,
+    gateway,
     "execute",
-    {
-      input: approveInput,
-    },
-    { attachedDeposit: "0" }
+    { input },
+    { attachedDeposit: "0", gas: "300000000000000" }
   );
 
   t.deepEqual(result, [true]);
 
-  const isApprovedBefore = await contract.view("is_contract_call_approved", {
-    command_id: commandId,
-    source_chain: sourceChain,
-    source_address: sourceAddress,
-    contract_address: executableContract.accountId,
-    payload_hash: payloadHash,
+  t.true(
+    await gateway.view("is_contract_call_approved", {
+      command_id: commandId,
+      source_chain: sourceChain,
+      source_address: sourceAddress,
+      contract_address: gateway.accountId,
+      payload_hash: payloadHash,
+    })
+  );
+});
+
+test("Gateway - should roll back an operatorship transfer the auth module rejected", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const gateway = await root.createSubAccount("axelar_gateway");
+
+  await gateway.deploy(path.join(__dirname, "../../dist/axelar_cgp_near.wasm"));
+
+  // The auth module is not owned by the gateway, it rejects the transfer
+  await root.call(gateway, "new", {
+    recent_operators: [],
+    config: {
+      auth_module: contract.accountId,
+    },
   });
 
-  t.is(isApprovedBefore, true);
+  const commandId = Utils.getRandomID();
 
-  await contract.call(
-    executableContract,
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(wallets.slice(0, 2)),
+    [1, 1],
+    2
+  );
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [commandId],
+    ["transferOperatorship"],
+    [params]
+  );
+
+  const tx = await root.callRaw(
+    gateway,
     "execute",
     {
-      command_id: commandId,
-      source_chain: sourceChain,
-      source_address: sourceAddress,
-      payload,
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        operators,
+        operators.map(() => 1),
+        threshold,
+        operators.slice(0, threshold)
+      ),
+    },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(
+    tx.receiptFailureMessages.some((message) => message.includes("Owner only"))
+  );
+
+  const events = tx.result.receipts_outcome
+    .flatMap((receipt) => receipt.outcome.logs)
+    .filter((log) => log.includes("axelar_near"))
+    .map((log) => JSON.parse(log.slice(11)));
+
+  t.deepEqual(
+    events.find(({ event }) => event === "command_failed_event")?.data,
+    { command_id: commandId, batch_index: 0 }
+  );
+  t.false(await gateway.view("is_command_executed", { command_id: commandId }));
+  t.false(await contract.view("is_current_operators", { params }));
+});
+
+test("Gateway - should validate a large operator set with the configured gas", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  // The default gas of the proof validation covers about 10 signers
+  const largeOperators = sortBy(
+    Array.from(
+      { length: 50 },
+      (_, i) => new ethers.Wallet(ethers.utils.id(`large-operator-${i}`))
+    ),
+    (wallet) => wallet.address.toLowerCase()
+  ) as unknown as SignerWithAddress[];
+
+  const largeWeights = largeOperators.map(() => 1);
+
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    {
+      params: await Utils.getTransferWeightedOperatorshipCommand(
+        Utils.getAddresses(largeOperators),
+        largeWeights,
+        largeOperators.length
+      ),
     },
     { attachedDeposit: "0" }
   );
 
-  const isApprovedAfter = await contract.view("is_contract_call_approved", {
-    command_id: commandId,
-    source_chain: sourceChain,
-    source_address: sourceAddress,
-    contract_address: executableContract.accountId,
-    payload_hash: payloadHash,
+  const deployGateway = async (name: string, gasConfig: object) => {
+    const gateway = await root.createSubAccount(name);
+
+    await gateway.deploy(
+      path.join(__dirname, "../../dist/axelar_cgp_near.wasm")
+    );
+
+    await root.call(gateway, "new", {
+      recent_operators: [],
+      config: {
+        auth_module: contract.accountId,
+        ...gasConfig,
+      },
+    });
+
+    return gateway;
+  };
+
+  const getInput = async (gateway: NearAccount) => {
+    const commandId = Utils.getRandomID();
+
+    const data = await Utils.buildCommandBatch(
+      CHAIN_ID,
+      [commandId],
+      ["approveContractCall"],
+      [
+        await Utils.getApproveContractCall(
+          "Polygon",
+          "address0x123",
+          gateway.accountId,
+          ethers.utils.keccak256("0x123abc"),
+          ethers.utils.keccak256("0x123abc123abc"),
+          0
+        ),
+      ]
+    );
+
+    return {
+      commandId,
+      input: await Utils.getSignedWeightedExecuteInput(
+        data,
+        largeOperators,
+        largeWeights,
+        largeOperators.length,
+        largeOperators
+      ),
+    };
+  };
+
+  const defaultGateway = await deployGateway("axelar_default_gas", {});
+
+  t.is(await defaultGateway.view("validate_proof_gas", {}), "10000000000000");
+  t.is(await defaultGateway.view("callback_gas", {}), "10000000000000");
+
+  const underProvisioned = await getInput(defaultGateway);
+
+  const underProvisionedTx = await root.callRaw(
+    defaultGateway,
+    "execute",
+    { input: underProvisioned.input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(underProvisionedTx.failed);
+  t.true(
+    underProvisionedTx.receiptFailureMessages.some((message) =>
+      message.includes("Exceeded the prepaid gas")
+    )
+  );
+  t.false(
+    await defaultGateway.view("is_command_executed", {
+      command_id: underProvisioned.commandId,
+    })
+  );
+
+  const configuredGateway = await deployGateway("axelar_configured_gas", {
+    validate_proof_gas: "60000000000000",
+    callback_gas: "20000000000000",
   });
 
-  t.is(isApprovedAfter, false);
+  t.is(
+    await configuredGateway.view("validate_proof_gas", {}),
+    "60000000000000"
+  );
+  t.is(await configuredGateway.view("callback_gas", {}), "20000000000000");
 
-  const value = await executableContract.view("get_value", {});
+  const configured = await getInput(configuredGateway);
 
-  const contractSourceChain = await executableContract.view(
-    "get_source_chain",
-    {}
+  t.deepEqual(
+    await root.call(
+      configuredGateway,
+      "execute",
+      { input: configured.input },
+      { attachedDeposit: "0", gas: "300000000000000" }
+    ),
+    [true]
   );
-  const contractSourceAddress = await executableContract.view(
-    "get_source_address",
-    {}
+  t.true(
+    await configuredGateway.view("is_command_executed", {
+      command_id: configured.commandId,
+    })
   );
 
-  t.is(value, "Hello from Polygon!");
-  t.is(contractSourceChain, sourceChain);
-  t.is(contractSourceAddress, sourceAddress);
+  // The configured gas must be attached up front
+  const error = await t.throwsAsync(
+    root.call(
+      configuredGateway,
+      "execute",
+      { input: (await getInput(configuredGateway)).input },
+      { attachedDeposit: "0", gas: "50000000000000" }
+    )
+  );
+
+  t.true(error?.message.includes("Not enough gas to validate the proof"));
 });
 
-test("Gateway - should emit contract called event in a cross contract call", async (t) => {
-  const { worker, root, contract, executableContract } = t.context.accounts;
+test("Gateway - should report operators that drifted from the auth module", async (t) => {
+  const { contract, root } = t.context.accounts;
 
-  const payload = ethers.utils.defaultAbiCoder.encode(
-    ["string"],
-    ["Hello from Near!"]
+  const gateway = await root.createSubAccount("axelar_gateway");
+
+  await gateway.deploy(path.join(__dirname, "../../dist/axelar_cgp_near.wasm"));
+
+  const params = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(operators),
+    operators.map(() => 1),
+    threshold
   );
 
-  const payloadHash = ethers.utils.keccak256(payload);
+  // The gateway is seeded separately with the current operators of the auth module
+  await root.call(gateway, "new", {
+    recent_operators: [params],
+    config: {
+      auth_module: contract.accountId,
+    },
+  });
 
-  const chain = "ethereum";
-  const destinationAddress = "address0x123";
-  const value = "Hello from Near!";
+  const matchesAuth = (params: string) =>
+    root.call(
+      gateway,
+      "operators_hash_matches_auth",
+      { params },
+      { gas: "300000000000000" }
+    );
 
-  const tx = await executableContract.callRaw(
-    executableContract,
-    "set",
-    {
-      chain,
-      destination_address: destinationAddress,
-      value,
+  t.true(await matchesAuth(params));
+
+  const newOperators = sortBy(wallets.slice(0, 4), (wallet) =>
+    wallet.address.toLowerCase()
+  );
+
+  const newParams = await Utils.getTransferWeightedOperatorshipCommand(
+    Utils.getAddresses(newOperators),
+    newOperators.map(() => 1),
+    threshold
+  );
+
+  // Only the auth module is rotated
+  await root.call(
+    contract,
+    "transfer_operatorship",
+    { params: newParams },
+    { attachedDeposit: "0" }
+  );
+
+  t.false(await matchesAuth(params));
+  t.false(await matchesAuth(newParams));
+});
+
+test("Gateway - should only allow the owner to set the auth module", async (t) => {
+  const { contract, root, john } = t.context.accounts;
+
+  const error = await t.throwsAsync(
+    john.call(
+      contract,
+      "set_auth_module",
+      { auth_module: john.accountId },
+      { attachedDeposit: "0" }
+    )
+  );
+
+  t.true(error?.message.includes("Owner only"));
+
+  await root.call(
+    contract,
+    "set_auth_module",
+    { auth_module: john.accountId },
+    { attachedDeposit: "0" }
+  );
+
+  t.is(await contract.view("auth_module", {}), john.accountId);
+});
+
+test("Gateway - should only execute the batches whose proof the auth module accepts", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const gateway = await root.createSubAccount("axelar_gateway");
+
+  await gateway.deploy(path.join(__dirname, "../../dist/axelar_cgp_near.wasm"));
+
+  await root.call(gateway, "new", {
+    recent_operators: [],
+    config: {
+      auth_module: contract.accountId,
     },
-    {
-      attachedDeposit: "0",
-    }
+  });
+
+  const unknownOperators = wallets.slice(0, 3);
+
+  const getInput = async (signers: SignerWithAddress[]) => {
+    const commandId = Utils.getRandomID();
+
+    const data = await Utils.buildCommandBatch(
+      CHAIN_ID,
+      [commandId],
+      ["approveContractCall"],
+      [
+        await Utils.getApproveContractCall(
+          "Polygon",
+          "address0x123",
+          gateway.accountId,
+          ethers.utils.keccak256("0x123abc"),
+          ethers.utils.keccak256("0x123abc123abc"),
+          0
+        ),
+      ]
+    );
+
+    const input = await Utils.getSignedWeightedExecuteInput(
+      data,
+      signers,
+      signers.map(() => 1),
+      threshold,
+      signers.slice(0, threshold)
+    );
+
+    return { commandId, input };
+  };
+
+  const valid = await getInput(operators);
+
+  const validTx = await root.callRaw(
+    gateway,
+    "execute",
+    { input: valid.input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.false(validTx.failed);
+  t.deepEqual(validTx.parseResult(), [true]);
+  t.true(
+    await gateway.view("is_command_executed", { command_id: valid.commandId })
+  );
+
+  const invalid = await getInput(unknownOperators);
+
+  const invalidTx = await root.callRaw(
+    gateway,
+    "execute",
+    { input: invalid.input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(invalidTx.failed);
+  t.true(
+    invalidTx.receiptFailureMessages.some((message) =>
+      message.includes("Invalid proof")
+    )
+  );
+  t.false(
+    await gateway.view("is_command_executed", { command_id: invalid.commandId })
+  );
+});
+
+test("Gateway - should validate the proof against the EIP-191 hash of the batch", async (t) => {
+  const { contract, root } = t.context.accounts;
+
+  const unknownOperators = wallets.slice(0, 3);
+
+  const data = await Utils.buildCommandBatch(
+    CHAIN_ID,
+    [ethers.utils.id("command-id")],
+    ["unknownCommand"],
+    [ethers.utils.defaultAbiCoder.encode(["string"], ["Polygon"])]
+  );
+
+  // Reference values of the EVM gateway, `ECDSA.toEthSignedMessageHash(keccak256(data))`
+  const expectedMessageHash =
+    "0x4fa96c6239d1ba6430ec5729f416a195090957df4f7d8c44588f22006f7bb036";
+
+  t.is(
+    ethers.utils.hashMessage(ethers.utils.arrayify(ethers.utils.keccak256(data))),
+    expectedMessageHash
   );
 
+  const input = await Utils.getSignedWeightedExecuteInput(
+    data,
+    unknownOperators,
+    unknownOperators.map(() => 1),
+    threshold,
+    unknownOperators
+  );
+
+  const tx = await root.callRaw(
+    contract,
+    "execute",
+    { input },
+    { attachedDeposit: "0", gas: "300000000000000" }
+  );
+
+  t.true(tx.failed);
+
   const events = tx.result.receipts_outcome
     .map((receipt) => receipt.outcome.logs.map((log) => log))
     .flatMap((log) => log)
     .filter((log) => log.includes("axelar_near"))
     .map((event) => JSON.parse(event.slice(11)));
 
-  t.is(events[0].standard, "axelar_near");
-  t.is(events[0].version, "1.0.0");
-  t.is(events[0].event, "contract_call_event");
-  t.is(events[0].data.destination_chain, chain);
-  t.is(events[0].data.destination_contract_address, destinationAddress);
-  t.is(events[0].data.payload, payload);
-  t.is(events[0].data.payload_hash, payloadHash);
+  t.is(events.length, 1);
+  t.is(events[0].event, "proof_validation_failed_event");
+  t.is(events[0].data.message_hash, expectedMessageHash);
+  t.is(events[0].data.reason, "unknown_operators");
 });
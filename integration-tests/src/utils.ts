@@ -1,8 +1,23 @@
 const { ethers } = require("hardhat");
 import { SignerWithAddress } from "@nomiclabs/hardhat-ethers/signers";
+import { BigNumberish } from "ethers";
 import { sortBy } from "lodash";
 
+const SECP256K1_N = ethers.BigNumber.from(
+  "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+);
+
+// Domain the auth module hashes the signed operatorship transfers under
+const OPERATORSHIP_TRANSFER_DOMAIN = ethers.utils.id(
+  "axelar-near-operatorship-transfer"
+);
+
+// The threshold flag of a fraction of the total weight in basis points
+const FRACTIONAL_THRESHOLD_FLAG = ethers.BigNumber.from(2).pow(255);
+
 class Utils {
+  static OPERATORSHIP_TRANSFER_DOMAIN = OPERATORSHIP_TRANSFER_DOMAIN;
+
   static getAddresses = (signers: SignerWithAddress[]) =>
     signers.map(({ address }) => address);
 
@@ -17,22 +32,83 @@ class Utils {
     threshold: number,
     signers: SignerWithAddress[]
   ) => {
+    const signatures = await Utils.getSignatures(data, signers);
+    return Utils.getWeightedProof(
+      Utils.getAddresses(operators),
+      weights,
+      threshold,
+      signatures
+    );
+  };
+
+  static getWeightedProof = (
+    operators: string[],
+    weights: number[],
+    threshold: number,
+    signatures: string[]
+  ) => {
+    return ethers.utils.defaultAbiCoder.encode(
+      ["address[]", "uint256[]", "uint256", "bytes[]"],
+      [operators, weights, threshold, signatures]
+    );
+  };
+
+  // The data as it is hashed under a domain, before being signed as an Ethereum message
+  static getDomainData = (domain: string, data: string) =>
+    ethers.utils.hexConcat([domain, data]);
+
+  // The data the current operators sign to transfer the operatorship of the contract at the epoch
+  static getOperatorshipTransferData = (
+    accountId: string,
+    epoch: number,
+    params: string
+  ) =>
+    Utils.getDomainData(
+      OPERATORSHIP_TRANSFER_DOMAIN,
+      ethers.utils.defaultAbiCoder.encode(
+        ["string", "uint256", "bytes"],
+        [accountId, epoch, params]
+      )
+    );
+
+  static getSignatures = async (data: string, signers: SignerWithAddress[]) => {
     const hash = ethers.utils.arrayify(ethers.utils.keccak256(data));
-    const signatures = await Promise.all(
+    return Promise.all(
       sortBy(signers, (wallet) => wallet.address.toLowerCase()).map((wallet) =>
         wallet.signMessage(hash)
       )
     );
-    return ethers.utils.defaultAbiCoder.encode(
-      ["address[]", "uint256[]", "uint256", "bytes[]"],
-      [Utils.getAddresses(operators), weights, threshold, signatures]
-    );
   };
 
+  // Flips a signature to the malleable upper half of the curve order
+  static getHighSSignature = (signature: string) => {
+    const { r, s, v } = ethers.utils.splitSignature(signature);
+    const highS = SECP256K1_N.sub(s);
+    return ethers.utils.hexConcat([
+      r,
+      ethers.utils.hexZeroPad(highS.toHexString(), 32),
+      v === 27 ? "0x1c" : "0x1b",
+    ]);
+  };
+
+  static getSignatureWithRecoveryByte = (signature: string, v: number) => {
+    return ethers.utils.hexConcat([
+      ethers.utils.hexDataSlice(signature, 0, 64),
+      ethers.utils.hexlify(v),
+    ]);
+  };
+
+  // EIP-2098 compact form, the recovery bit is carried in the top bit of `s`
+  static getCompactSignature = (signature: string) =>
+    ethers.utils.splitSignature(signature).compact;
+
+  static getFractionalThreshold = (basisPoints: number) =>
+    FRACTIONAL_THRESHOLD_FLAG.add(basisPoints);
+
   static getTransferWeightedOperatorshipCommand = async (
     newOperators: string[],
     newWeights: number[],
-    threshold: number
+    threshold: BigNumberish
   ) => {
     return ethers.utils.defaultAbiCoder.encode(
       ["address[]", "uint256[]", "uint256"],
@@ -65,6 +141,31 @@ class Utils {
     );
   };
 
+  static getDeployTokenCommand = async (
+    name: string,
+    symbol: string,
+    decimals: number,
+    cap: number,
+    tokenAddress: string,
+    mintLimit: number
+  ) => {
+    return ethers.utils.defaultAbiCoder.encode(
+      ["string", "string", "uint8", "uint256", "string", "uint256"],
+      [name, symbol, decimals, cap, tokenAddress, mintLimit]
+    );
+  };
+
+  static getMintCommand = async (
+    symbol: string,
+    account: string,
+    amount: number
+  ) => {
+    return ethers.utils.defaultAbiCoder.encode(
+      ["string", "string", "uint256"],
+      [symbol, account, amount]
+    );
+  };
+
   static buildCommandBatch = async (
     chainId: number,
     commandIDs: string[],
@@ -100,6 +201,32 @@ class Utils {
       ]
     );
   };
+  static getLegacyState = (currentEpoch: number) => {
+    const u64 = (value: number) => {
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(value));
+      return buffer;
+    };
+    const prefix = (value: string) => {
+      const length = Buffer.alloc(4);
+      length.writeUInt32LE(value.length);
+      return Buffer.concat([length, Buffer.from(value)]);
+    };
+    const keccak = (value: string) =>
+      Buffer.from(
+        ethers.utils.arrayify(ethers.utils.keccak256(Buffer.from(value)))
+      );
+
+    // Borsh layout of the `1.0.0` contract, before `old_key_retention` was stored
+    return Buffer.concat([
+      u64(currentEpoch),
+      prefix("hash_for_epoch"),
+      prefix("epoch_for_hash"),
+      keccak("command-executed"),
+      keccak("contract-call-approved"),
+      prefix("bool_state"),
+    ]);
+  };
 }
 
 export default Utils;
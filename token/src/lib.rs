@@ -0,0 +1,116 @@
+/*
+ * Axelar Token contract
+ *
+ */
+
+use near_contract_tools::standard::nep141::Nep141Controller;
+use near_contract_tools::standard::nep148::{FungibleTokenMetadata, Nep148, FT_METADATA_SPEC};
+use near_contract_tools::{owner::*, Nep141, Owner};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, PanicOnDefault};
+
+/// `AxelarToken` is the NEP-141 token deployed by the gateway for the `deployToken` command, the
+/// gateway is its owner and the only account allowed to mint and burn.
+///
+/// Properties:
+///
+/// * `name`: The name of the token.
+/// * `symbol`: The symbol of the token, unique within the gateway.
+/// * `decimals`: The number of decimals of the token.
+/// * `cap`: The maximum total supply, unbounded if 0.
+#[near_bindgen]
+#[derive(Owner, Nep141, PanicOnDefault, BorshDeserialize, BorshSerialize)]
+#[nep141(no_hooks)]
+pub struct AxelarToken {
+    name: String,
+    symbol: String,
+    decimals: u8,
+    cap: Balance,
+}
+
+#[near_bindgen]
+impl AxelarToken {
+    /// `new` is called by the gateway when the token is deployed, the gateway becomes the owner
+    ///
+    /// Arguments:
+    ///
+    /// * `name`: The name of the token.
+    /// * `symbol`: The symbol of the token.
+    /// * `decimals`: The number of decimals of the token.
+    /// * `cap`: The maximum total supply, 0 for no limit.
+    ///
+    /// Returns:
+    ///
+    /// The contract is being returned.
+    #[init]
+    pub fn new(name: String, symbol: String, decimals: u8, cap: U128) -> Self {
+        let mut contract = Self {
+            name,
+            symbol,
+            decimals,
+            cap: cap.into(),
+        };
+
+        Owner::init(&mut contract, &env::predecessor_account_id());
+
+        contract
+    }
+
+    /// `cap` returns the maximum total supply of the token
+    ///
+    /// Returns:
+    ///
+    /// The cap, 0 if the supply is unbounded.
+    pub fn cap(&self) -> U128 {
+        self.cap.into()
+    }
+
+    // Only owner
+
+    /// `mint` creates new tokens for an account, emitting the NEP-141 `ft_mint` event
+    ///
+    /// Arguments:
+    ///
+    /// * `account_id`: The account that receives the tokens.
+    /// * `amount`: The amount of tokens to mint.
+    pub fn mint(&mut self, account_id: AccountId, amount: U128) {
+        Self::require_owner();
+
+        let total_supply = Self::total_supply()
+            .checked_add(amount.into())
+            .unwrap_or_else(|| env::panic_str("Total supply overflow"));
+
+        require!(self.cap == 0 || total_supply <= self.cap, "Cap exceeded");
+
+        Nep141Controller::mint(self, account_id, amount.into(), None);
+    }
+
+    /// `burn` destroys tokens of an account, emitting the NEP-141 `ft_burn` event
+    ///
+    /// Arguments:
+    ///
+    /// * `account_id`: The account the tokens are burnt from.
+    /// * `amount`: The amount of tokens to burn.
+    pub fn burn(&mut self, account_id: AccountId, amount: U128) {
+        Self::require_owner();
+
+        Nep141Controller::burn(self, account_id, amount.into(), None);
+    }
+}
+
+#[near_bindgen]
+impl Nep148 for AxelarToken {
+    /// `ft_metadata` returns the NEP-148 metadata of the token
+    fn ft_metadata(&self) -> FungibleTokenMetadata {
+        FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            decimals: self.decimals,
+        }
+    }
+}